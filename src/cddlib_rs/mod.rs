@@ -4,19 +4,66 @@ use crate::sets::errors::SetOperationError;
 use cdd_bindings::*;
 use ndarray::{Array1, Array2};
 use once_cell::sync::Lazy;
-use std::os::raw::c_long;
+use std::os::raw::{c_long, c_ulong};
 use std::sync::Mutex;
 
 static CDD_MUTEX: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
 
+/// Vertices, extreme rays and lines of a polyhedron, one per row.
+/// The polyhedron is the convex hull of the vertices plus the cone of the rays and the
+/// linear span of the lines.
+#[derive(Clone, Debug)]
+pub struct PolyhedronGenerators {
+    pub vertices: Array2<f64>,
+    pub rays: Array2<f64>,
+    pub lines: Array2<f64>,
+}
+
+impl PolyhedronGenerators {
+    pub fn is_bounded(&self) -> bool {
+        self.rays.nrows() == 0 && self.lines.nrows() == 0
+    }
+}
+
+/// Inequalities $ax \leq b$ and equalities $a_e x = b_e$ of a polyhedron, one per row.
+#[derive(Clone, Debug)]
+pub struct PolyhedronHalfspaces {
+    pub a: Array2<f64>,
+    pub b: Array1<f64>,
+    pub ae: Array2<f64>,
+    pub be: Array1<f64>,
+}
+
 /// Computes the vertices of a polytope defined by the inequality Ax ≤ b.
 ///
 /// This function uses rust bindings of the cddlib library to convert the H-representation
 /// (halfspace representation) to V-representation (vertex representation).
+/// Rays and lines of unbounded polyhedra are dropped, use [`compute_polyhedron_generators`]
+/// to obtain them.
 pub fn compute_polytope_vertices(
     a: &Array2<f64>,
     b: &Array1<f64>,
 ) -> Result<Array2<f64>, SetOperationError> {
+    Ok(compute_polyhedron_generators(a, b)?.vertices)
+}
+
+/// Computes the vertices, extreme rays and lines of the polyhedron Ax ≤ b with cddlib.
+pub fn compute_polyhedron_generators(
+    a: &Array2<f64>,
+    b: &Array1<f64>,
+) -> Result<PolyhedronGenerators, SetOperationError> {
+    let dim = a.ncols();
+    compute_polyhedron_generators_with_equalities(a, b, &Array2::zeros((0, dim)), &Array1::zeros(0))
+}
+
+/// Computes the vertices, extreme rays and lines of the polyhedron Ax ≤ b, Ae x = be with
+/// cddlib. The equality constraints are passed as the linearity set of the cddlib matrix.
+pub fn compute_polyhedron_generators_with_equalities(
+    a: &Array2<f64>,
+    b: &Array1<f64>,
+    ae: &Array2<f64>,
+    be: &Array1<f64>,
+) -> Result<PolyhedronGenerators, SetOperationError> {
     // Check dimension compatibility
     if a.nrows() != b.len() {
         return Err(SetOperationError::DimensionMismatch {
@@ -24,6 +71,18 @@ pub fn compute_polytope_vertices(
             got: b.len(),
         });
     }
+    if ae.nrows() != be.len() {
+        return Err(SetOperationError::DimensionMismatch {
+            expected: ae.nrows(),
+            got: be.len(),
+        });
+    }
+    if ae.ncols() != a.ncols() {
+        return Err(SetOperationError::DimensionMismatch {
+            expected: a.ncols(),
+            got: ae.ncols(),
+        });
+    }
 
     // Due to the global_constants calls, we need to ensure that only one thread
     // is executing this at a time.
@@ -31,16 +90,20 @@ pub fn compute_polytope_vertices(
 
     unsafe {
         dd_set_global_constants();
-        let result = compute_polytope_vertices_inner(a, b);
+        let a = ndarray::concatenate![ndarray::Axis(0), *a, *ae];
+        let b = ndarray::concatenate![ndarray::Axis(0), *b, *be];
+        let result = compute_polyhedron_generators_inner(&a, &b, ae.nrows());
         dd_free_global_constants();
         result
     }
 }
 
-unsafe fn compute_polytope_vertices_inner(
+/// The last `n_equalities` rows of `a` and `b` are equality constraints.
+unsafe fn compute_polyhedron_generators_inner(
     a: &Array2<f64>,
     b: &Array1<f64>,
-) -> Result<Array2<f64>, SetOperationError> {
+    n_equalities: usize,
+) -> Result<PolyhedronGenerators, SetOperationError> {
     let m = a.nrows() as c_long;
     let n = a.ncols() as c_long;
     // Initialize cddlib
@@ -56,6 +119,10 @@ unsafe fn compute_polytope_vertices_inner(
 
         (*mat).representation = dd_RepresentationType::dd_Inequality;
 
+        for i in (m - n_equalities as c_long)..m {
+            set_addelem((*mat).linset, i + 1);
+        }
+
         for i in 0..m {
             let i_usize = i as usize;
             let i_isize = i as isize;
@@ -96,8 +163,10 @@ unsafe fn compute_polytope_vertices_inner(
             });
         }
 
+        // Rows [1 | v] are vertices, rows [0 | r] are rays, or lines if in the linearity set
         let mut vertices_data = Vec::new();
-        let mut vertex_count = 0;
+        let mut rays_data = Vec::new();
+        let mut lines_data = Vec::new();
 
         for i in 0..(*gens).rowsize {
             let i_isize = i as isize;
@@ -105,14 +174,17 @@ unsafe fn compute_polytope_vertices_inner(
             let kind_ptr = (*gen_row_ptr).offset(0);
             let kind = (*kind_ptr)[0];
 
-            if (kind - 1.0).abs() < 1e-9 {
-                // It's a vertex
-                for j in 1..=n {
-                    let j_isize = j as isize;
-                    let vertex_ptr = (*gen_row_ptr).offset(j_isize);
-                    vertices_data.push((*vertex_ptr)[0]);
-                }
-                vertex_count += 1;
+            let data = if (kind - 1.0).abs() < 1e-9 {
+                &mut vertices_data
+            } else if set_member(i + 1, (*gens).linset) {
+                &mut lines_data
+            } else {
+                &mut rays_data
+            };
+            for j in 1..=n {
+                let j_isize = j as isize;
+                let value_ptr = (*gen_row_ptr).offset(j_isize);
+                data.push((*value_ptr)[0]);
             }
         }
 
@@ -120,17 +192,163 @@ unsafe fn compute_polytope_vertices_inner(
         dd_FreePolyhedra(poly);
         dd_FreeMatrix(mat);
         dd_FreeMatrix(gens);
+
+        let to_array = |data: Vec<f64>| {
+            Array2::from_shape_vec((data.len() / n as usize, n as usize), data).map_err(|e| {
+                SetOperationError::DataConversionError {
+                    source: format!("Failed to create Array2 from generator data: {}", e).into(),
+                }
+            })
+        };
+
+        Ok(PolyhedronGenerators {
+            vertices: to_array(vertices_data)?,
+            rays: to_array(rays_data)?,
+            lines: to_array(lines_data)?,
+        })
+    }
+}
+
+/// Computes the halfspaces Ax ≤ b and equalities Ae x = be of the convex hull of the given
+/// vertices (one per row).
+///
+/// This function uses rust bindings of the cddlib library to convert the V-representation
+/// (vertex representation) to H-representation (halfspace representation). The linearity set
+/// of cddlib, i.e. the affine hull of lower-dimensional polytopes, is returned as equalities.
+pub fn compute_polytope_halfspaces(
+    vertices: &Array2<f64>,
+) -> Result<PolyhedronHalfspaces, SetOperationError> {
+    if vertices.nrows() == 0 {
+        return Err(SetOperationError::EmptySet);
+    }
+
+    let _guard = CDD_MUTEX.lock().unwrap();
+
+    unsafe {
+        dd_set_global_constants();
+        let result = compute_polytope_halfspaces_inner(vertices);
         dd_free_global_constants();
+        result
+    }
+}
 
-        // Convert to Array2
-        if vertex_count == 0 {
-            Ok(Array2::zeros((0, n as usize)))
-        } else {
-            Array2::from_shape_vec((vertex_count, n as usize), vertices_data).map_err(|e| {
+unsafe fn compute_polytope_halfspaces_inner(
+    vertices: &Array2<f64>,
+) -> Result<PolyhedronHalfspaces, SetOperationError> {
+    let m = vertices.nrows() as c_long;
+    let n = vertices.ncols() as c_long;
+    // Create matrix [1 | V], the leading 1 marks each row as a point (0 would be a ray)
+    unsafe {
+        let mat = dd_CreateMatrix(m, n + 1);
+        if mat.is_null() {
+            return Err(SetOperationError::DataConversionError {
+                source: "Failed to create cddlib matrix".into(),
+            });
+        }
+
+        (*mat).representation = dd_RepresentationType::dd_Generator;
+
+        for i in 0..m {
+            let row_ptr = (*mat).matrix.offset(i as isize);
+            (*(*row_ptr).offset(0))[0] = 1.0;
+
+            for j in 0..n {
+                let coeff_ptr = (*row_ptr).offset((j + 1) as isize);
+                (*coeff_ptr)[0] = vertices[[i as usize, j as usize]];
+            }
+        }
+
+        let mut err: dd_ErrorType = dd_ErrorType::dd_NoError;
+        let poly = dd_DDMatrix2Poly(mat, &mut err);
+        if err != dd_ErrorType::dd_NoError || poly.is_null() {
+            dd_FreeMatrix(mat);
+            return Err(SetOperationError::InfeasibleOptimization {
+                source: format!("cddlib error: {:?}", err).into(),
+            });
+        }
+
+        // Extract inequalities [b | -A], i.e. b - Ax >= 0
+        let ineqs = dd_CopyInequalities(poly);
+        if ineqs.is_null() {
+            dd_FreePolyhedra(poly);
+            dd_FreeMatrix(mat);
+            return Err(SetOperationError::DataConversionError {
+                source: "Failed to extract inequalities from polyhedron".into(),
+            });
+        }
+
+        let mut a_data = Vec::new();
+        let mut b_data = Vec::new();
+        let mut ae_data = Vec::new();
+        let mut be_data = Vec::new();
+
+        for i in 0..(*ineqs).rowsize {
+            let row_ptr = (*ineqs).matrix.offset(i as isize);
+            let rhs = (*(*row_ptr).offset(0))[0];
+            let coeffs: Vec<f64> = (1..=n)
+                .map(|j| -(*(*row_ptr).offset(j as isize))[0])
+                .collect();
+
+            // Skip the trivial row 1 >= 0 that cddlib adds for bounded polytopes
+            if coeffs.iter().all(|c| c.abs() < 1e-12) {
+                continue;
+            }
+
+            // Rows in the linearity set are equalities
+            if set_member(i + 1, (*ineqs).linset) {
+                ae_data.extend(coeffs);
+                be_data.push(rhs);
+            } else {
+                a_data.extend(coeffs);
+                b_data.push(rhs);
+            }
+        }
+
+        // Cleanup
+        dd_FreePolyhedra(poly);
+        dd_FreeMatrix(mat);
+        dd_FreeMatrix(ineqs);
+
+        let to_array = |rows: usize, data: Vec<f64>| {
+            Array2::from_shape_vec((rows, n as usize), data).map_err(|e| {
                 SetOperationError::DataConversionError {
-                    source: format!("Failed to create Array2 from vertices data: {}", e).into(),
+                    source: format!("Failed to create Array2 from halfspace data: {}", e).into(),
                 }
             })
+        };
+
+        Ok(PolyhedronHalfspaces {
+            a: to_array(b_data.len(), a_data)?,
+            b: Array1::from_vec(b_data),
+            ae: to_array(be_data.len(), ae_data)?,
+            be: Array1::from_vec(be_data),
+        })
+    }
+}
+
+/// Checks membership of the 1-based `elem` in a cddlib set.
+///
+/// Mirrors `set_member` from cddlib's setoper, which is not part of the bindings: the first
+/// block stores the ground set size, the following blocks the members as a bitset.
+unsafe fn set_member(elem: c_long, set: set_type) -> bool {
+    let block_bits = (std::mem::size_of::<c_ulong>() * 8) as c_long;
+    unsafe {
+        if set.is_null() || elem < 1 || elem as c_ulong > *set {
+            return false;
+        }
+        let block = *set.offset(((elem - 1) / block_bits + 1) as isize);
+        block & (1 << ((elem - 1) % block_bits)) != 0
+    }
+}
+
+/// Adds the 1-based `elem` to a cddlib set, mirroring `set_addelem` from cddlib's setoper.
+unsafe fn set_addelem(set: set_type, elem: c_long) {
+    let block_bits = (std::mem::size_of::<c_ulong>() * 8) as c_long;
+    unsafe {
+        if set.is_null() || elem < 1 || elem as c_ulong > *set {
+            return;
         }
+        let block = set.offset(((elem - 1) / block_bits + 1) as isize);
+        *block |= 1 << ((elem - 1) % block_bits);
     }
 }
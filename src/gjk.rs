@@ -0,0 +1,538 @@
+//! Distance and penetration queries between convex sets that only rely on
+//! [`GeoSet::support_function`].
+//!
+//! Both algorithms work on the Minkowski difference $D = A \oplus (-B)$, whose support function
+//! is $\rho_D(d) = \rho_A(d) + \rho_B(-d)$. The sets overlap if and only if $0 \in D$.
+//! The closest point of a single set is also available through the Frank–Wolfe algorithm.
+use crate::cddlib_rs::compute_polytope_halfspaces;
+use crate::linalg_utils::argmin;
+use crate::sets::GeoSet;
+use crate::sets::errors::SetOperationError;
+use ndarray::prelude::*;
+use ndarray_linalg::Solve;
+
+const MAX_ITERATIONS: usize = 1000;
+const TOLERANCE: f64 = 1e-10;
+
+/// Euclidean distance between two sets and a pair of closest points.
+/// Overlapping sets have distance zero and a common point as closest points.
+#[derive(Clone, Debug)]
+pub struct Proximity {
+    pub distance: f64,
+    pub point_a: Array1<f64>,
+    pub point_b: Array1<f64>,
+}
+
+/// Penetration depth of two overlapping sets.
+/// Translating the second set by `depth * normal` makes the sets touch.
+#[derive(Clone, Debug)]
+pub struct Penetration {
+    pub depth: f64,
+    pub normal: Array1<f64>,
+}
+
+/// Point of the Minkowski difference with the points of both sets it is made of
+#[derive(Clone, Debug)]
+struct SupportPoint {
+    w: Array1<f64>,
+    a: Array1<f64>,
+    b: Array1<f64>,
+}
+
+fn support_difference<A: GeoSet, B: GeoSet>(
+    a: &A,
+    b: &B,
+    direction: &Array1<f64>,
+) -> Result<(SupportPoint, f64), SetOperationError> {
+    let (point_a, value_a) = a.support_function(direction.clone())?;
+    let (point_b, value_b) = b.support_function(-direction)?;
+    let point = SupportPoint {
+        w: &point_a - &point_b,
+        a: point_a,
+        b: point_b,
+    };
+    Ok((point, value_a + value_b))
+}
+
+/// Point of minimum norm in the affine hull of `points`, given by its barycentric coordinates.
+/// Solves $\begin{bmatrix} W W^\top & 1 \\ 1^\top & 0 \end{bmatrix}
+/// \begin{bmatrix} \lambda \\ \mu \end{bmatrix} = \begin{bmatrix} 0 \\ 1 \end{bmatrix}$.
+fn affine_min_norm(points: &[&Array1<f64>]) -> Option<Array1<f64>> {
+    let k = points.len();
+    let mut system = Array2::<f64>::zeros((k + 1, k + 1));
+    for i in 0..k {
+        for j in 0..k {
+            system[[i, j]] = points[i].dot(points[j]);
+        }
+        system[[i, k]] = 1.0;
+        system[[k, i]] = 1.0;
+    }
+    let mut rhs = Array1::zeros(k + 1);
+    rhs[k] = 1.0;
+
+    let solution = system.solve(&rhs).ok()?;
+    let lambda = solution.slice(s![..k]).to_owned();
+    lambda.iter().all(|x| x.is_finite()).then_some(lambda)
+}
+
+/// Minimizer with its barycentric coordinates and the vertices of the face it lies in
+type FaceMinimizer = (Array1<f64>, Vec<f64>, Vec<SupportPoint>);
+
+/// Point of minimum norm in the convex hull of the simplex, computed with the signed volume
+/// variant of Johnson's subalgorithm.
+///
+/// If the affine minimizer has positive barycentric coordinates it is the minimizer of the
+/// simplex. Otherwise the minimum lies in a facet opposite a vertex with a non-positive
+/// coordinate, and only those facets are searched recursively. GJK keeps at most $n + 1$
+/// vertices in $\mathbb{R}^n$, which bounds the depth of the recursion by the dimension.
+/// Returns the minimizer, its barycentric coordinates and the vertices of the face.
+fn simplex_min_norm(simplex: &[SupportPoint]) -> Result<FaceMinimizer, SetOperationError> {
+    let points: Vec<_> = simplex.iter().map(|p| &p.w).collect();
+    let lambda = affine_min_norm(&points);
+
+    if let Some(lambda) = &lambda
+        && lambda.iter().all(|&l| l > 0.0)
+    {
+        let point = points
+            .iter()
+            .zip(lambda.iter())
+            .fold(Array1::zeros(simplex[0].w.dim()), |acc, (p, &l)| {
+                acc + *p * l
+            });
+        return Ok((point, lambda.to_vec(), simplex.to_vec()));
+    }
+
+    // Affinely dependent simplices are searched through all of their facets
+    let mut best: Option<FaceMinimizer> = None;
+    if simplex.len() > 1 {
+        for i in 0..simplex.len() {
+            if lambda.as_ref().is_some_and(|lambda| lambda[i] > 0.0) {
+                continue;
+            }
+            let facet: Vec<SupportPoint> = simplex
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, p)| p.clone())
+                .collect();
+            let candidate = simplex_min_norm(&facet)?;
+            if best
+                .as_ref()
+                .is_none_or(|(point, _, _)| candidate.0.dot(&candidate.0) < point.dot(point))
+            {
+                best = Some(candidate);
+            }
+        }
+    }
+
+    // Single points have a valid affine minimizer unless the points are not finite
+    best.ok_or_else(|| SetOperationError::UnsupportedOperation {
+        message: "no face of the simplex has a minimum norm point".to_string(),
+    })
+}
+
+/// Point of minimum norm in the convex hull of the points given as rows and its convex weights,
+/// computed with Wolfe's algorithm.
+///
+/// The corral is a set of affinely independent points whose affine minimizer has positive
+/// barycentric coordinates. The point $p_j$ minimizing $x^\top p_j$ enters the corral until
+/// $\|x\|^2 - x^\top p_j$ vanishes. If the new affine minimizer leaves the convex hull of the
+/// corral, the iterate moves towards it until a weight drops to zero and that point leaves.
+pub fn min_norm_point(
+    points: &Array2<f64>,
+) -> Result<(Array1<f64>, Array1<f64>), SetOperationError> {
+    let norms = points.map_axis(Axis(1), |p| p.dot(&p));
+    let start = argmin(&norms).ok_or(SetOperationError::EmptySet)?;
+    let scale = norms.fold(1.0_f64, |acc, &n| acc.max(n));
+
+    let mut corral = vec![start];
+    let mut weights = vec![1.0];
+    let mut x = points.row(start).to_owned();
+
+    for _ in 0..MAX_ITERATIONS {
+        let j = argmin(&points.dot(&x)).ok_or(SetOperationError::EmptySet)?;
+        if x.dot(&x) - x.dot(&points.row(j)) <= TOLERANCE * scale || corral.contains(&j) {
+            let mut convex_weights = Array1::zeros(points.nrows());
+            for (&i, &weight) in corral.iter().zip(&weights) {
+                convex_weights[i] = weight;
+            }
+            return Ok((x, convex_weights));
+        }
+        corral.push(j);
+        weights.push(0.0);
+
+        loop {
+            let corral_points: Vec<Array1<f64>> =
+                corral.iter().map(|&i| points.row(i).to_owned()).collect();
+            let lambda =
+                affine_min_norm(&corral_points.iter().collect::<Vec<_>>()).ok_or_else(|| {
+                    SetOperationError::UnsupportedOperation {
+                        message: "the corral is affinely dependent".to_string(),
+                    }
+                })?;
+
+            // Largest step towards the affine minimizer that keeps the weights non-negative
+            let Some((leaving, theta)) = weights
+                .iter()
+                .zip(lambda.iter())
+                .enumerate()
+                .filter(|(_, (_, l))| **l <= 0.0)
+                .map(|(i, (&w, &l))| (i, (w / (w - l)).max(0.0)))
+                .min_by(|(_, x), (_, y)| x.total_cmp(y))
+            else {
+                weights = lambda.to_vec();
+                break;
+            };
+            weights = weights
+                .iter()
+                .zip(lambda.iter())
+                .map(|(&w, &l)| (1.0 - theta) * w + theta * l)
+                .collect();
+            weights[leaving] = 0.0;
+
+            let kept: Vec<usize> = (0..corral.len()).filter(|&i| weights[i] > 0.0).collect();
+            corral = kept.iter().map(|&i| corral[i]).collect();
+            weights = kept.iter().map(|&i| weights[i]).collect();
+        }
+
+        x = corral
+            .iter()
+            .zip(&weights)
+            .fold(Array1::zeros(points.ncols()), |acc, (&i, &w)| {
+                acc + &points.row(i) * w
+            });
+    }
+
+    Err(SetOperationError::UnsupportedOperation {
+        message: "minimum norm point did not converge".to_string(),
+    })
+}
+
+/// Computes the point of the set closest to `point` with the fully corrective
+/// Frank–Wolfe algorithm.
+///
+/// The support vector $s$ along $p - x$ minimizes the linearization of $\|x - p\|^2$ over the
+/// set and is added to the atoms. The next iterate is the point of the convex hull of the atoms
+/// closest to $p$, and atoms with zero weight are dropped. The algorithm stops once the duality
+/// gap $(x - p)^\top (x - s)$ vanishes, which for polytopes happens after finitely many steps.
+pub fn frank_wolfe_closest_point<S: GeoSet>(
+    set: &S,
+    point: &Array1<f64>,
+) -> Result<Array1<f64>, SetOperationError> {
+    set._check_operand_dim(point.dim())?;
+
+    // Iterates and atoms are shifted by the point
+    let (initial, _) = set.support_function(Array1::ones(set.dim()))?;
+    let mut atoms = vec![initial - point];
+    let mut x = atoms[0].clone();
+
+    for _ in 0..MAX_ITERATIONS {
+        let x_norm = x.dot(&x);
+        if x_norm <= TOLERANCE * TOLERANCE {
+            return Ok(point + &x);
+        }
+
+        let (support, _) = set.support_function(-&x)?;
+        let atom = support - point;
+        if x_norm - x.dot(&atom) <= TOLERANCE * x_norm.max(1.0) {
+            return Ok(point + &x);
+        }
+        atoms.push(atom);
+
+        let views: Vec<_> = atoms.iter().map(|atom| atom.view()).collect();
+        let stacked = ndarray::stack(Axis(0), &views).map_err(|e| {
+            SetOperationError::DataConversionError {
+                source: Box::new(e),
+            }
+        })?;
+        let (minimizer, weights) = min_norm_point(&stacked)?;
+        atoms = atoms
+            .into_iter()
+            .zip(weights.iter())
+            .filter(|(_, w)| **w > 0.0)
+            .map(|(atom, _)| atom)
+            .collect();
+        x = minimizer;
+    }
+
+    Err(SetOperationError::UnsupportedOperation {
+        message: "Frank–Wolfe did not converge".to_string(),
+    })
+}
+
+/// Computes the Euclidean distance between two convex sets with the
+/// Gilbert–Johnson–Keerthi algorithm.
+///
+/// The point $v$ of minimum norm in the current simplex is improved by the support point
+/// $w$ of $D$ along $-v$ until $\|v\|^2 - v^\top w$ vanishes.
+pub fn distance<A: GeoSet, B: GeoSet>(a: &A, b: &B) -> Result<Proximity, SetOperationError> {
+    gjk(a, b, MAX_ITERATIONS)
+}
+
+/// Runs GJK for at most `max_iterations` iterations and fails if it has not converged by then
+fn gjk<A: GeoSet, B: GeoSet>(
+    a: &A,
+    b: &B,
+    max_iterations: usize,
+) -> Result<Proximity, SetOperationError> {
+    a._check_operand_dim(b.dim())?;
+    let dim = a.dim();
+    if dim == 0 {
+        return Err(SetOperationError::UnsupportedOperation {
+            message: "distance is not defined for zero-dimensional sets".to_string(),
+        });
+    }
+
+    let mut initial_direction = Array1::zeros(dim);
+    initial_direction[0] = 1.0;
+    let (initial, _) = support_difference(a, b, &initial_direction)?;
+
+    let mut v = initial.w.clone();
+    let mut lambda = vec![1.0];
+    let mut simplex = vec![initial];
+
+    let mut converged = false;
+    for _ in 0..max_iterations {
+        let v_norm = v.dot(&v);
+        // The origin is contained in the simplex
+        if v_norm <= TOLERANCE * TOLERANCE || simplex.len() > dim {
+            converged = true;
+            break;
+        }
+
+        let (w, _) = support_difference(a, b, &(-&v))?;
+        if v_norm - v.dot(&w.w) <= TOLERANCE * v_norm.max(1.0) {
+            converged = true;
+            break;
+        }
+
+        simplex.push(w);
+        (v, lambda, simplex) = simplex_min_norm(&simplex)?;
+    }
+    if !converged {
+        return Err(SetOperationError::UnsupportedOperation {
+            message: "GJK did not converge".to_string(),
+        });
+    }
+
+    let combine = |points: Vec<&Array1<f64>>| -> Array1<f64> {
+        points
+            .into_iter()
+            .zip(lambda.iter())
+            .fold(Array1::zeros(dim), |acc, (p, &l)| acc + p * l)
+    };
+    let point_a = combine(simplex.iter().map(|p| &p.a).collect());
+    let point_b = combine(simplex.iter().map(|p| &p.b).collect());
+
+    Ok(Proximity {
+        distance: v.dot(&v).sqrt(),
+        point_a,
+        point_b,
+    })
+}
+
+/// Computes the penetration depth of two overlapping convex sets with the
+/// expanding polytope algorithm. Returns `None` if the sets do not overlap.
+///
+/// The polytope spanned by support points of $D$ is expanded along the normal of its facet
+/// closest to the origin until the support function of $D$ confirms that facet.
+/// The facets of the polytope are enumerated by cddlib.
+pub fn penetration_depth<A: GeoSet, B: GeoSet>(
+    a: &A,
+    b: &B,
+) -> Result<Option<Penetration>, SetOperationError> {
+    if distance(a, b)?.distance > TOLERANCE.sqrt() {
+        return Ok(None);
+    }
+
+    let dim = a.dim();
+    let mut points: Vec<Array1<f64>> = Vec::new();
+    for i in 0..dim {
+        for sign in [1.0, -1.0] {
+            let mut direction = Array1::zeros(dim);
+            direction[i] = sign;
+            points.push(support_difference(a, b, &direction)?.0.w);
+        }
+    }
+
+    for _ in 0..MAX_ITERATIONS {
+        let mut vertices = Array2::zeros((points.len(), dim));
+        for (mut row, point) in vertices.outer_iter_mut().zip(points.iter()) {
+            row.assign(point);
+        }
+
+        let halfspaces = compute_polytope_halfspaces(&vertices)?;
+
+        // Expand a flat polytope along the normal of its affine hull. If the support function
+        // confirms the affine hull, the Minkowski difference is flat and the origin lies on
+        // its boundary.
+        if let Some(row) = halfspaces.ae.outer_iter().next() {
+            let normal = &row / row.dot(&row).sqrt();
+            let mut expanded = false;
+            for direction in [normal.clone(), -&normal] {
+                let (support, support_value) = support_difference(a, b, &direction)?;
+                if support_value - direction.dot(&points[0]) > 1e-9 {
+                    points.push(support.w);
+                    expanded = true;
+                }
+            }
+            if !expanded {
+                return Ok(Some(Penetration { depth: 0.0, normal }));
+            }
+            continue;
+        }
+
+        // Facet closest to the origin
+        let (normal, offset) = halfspaces
+            .a
+            .outer_iter()
+            .zip(halfspaces.b.iter())
+            .map(|(row, &b_i)| {
+                let norm = row.dot(&row).sqrt();
+                (&row / norm, b_i / norm)
+            })
+            .min_by(|(_, x), (_, y)| x.total_cmp(y))
+            .ok_or_else(|| SetOperationError::UnsupportedOperation {
+                message: "the Minkowski difference has no facets".to_string(),
+            })?;
+
+        let (support, support_value) = support_difference(a, b, &normal)?;
+        if support_value - offset <= 1e-9 {
+            return Ok(Some(Penetration {
+                depth: offset.max(0.0),
+                normal,
+            }));
+        }
+
+        points.push(support.w);
+    }
+
+    Err(SetOperationError::UnsupportedOperation {
+        message: "penetration depth did not converge".to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Ellipsoid, HPolytope, Interval, VPolytope, Zonotope};
+
+    #[test]
+    fn test_distance_boxes() {
+        let a = Interval::from_unit_box(2);
+        let b = HPolytope::from_unit_box(2)
+            .translate(&array![4.0, 0.5])
+            .unwrap();
+
+        let proximity = distance(&a, &b).unwrap();
+        assert!((proximity.distance - 2.0).abs() < 1e-9);
+        assert!((proximity.point_a[0] - 1.0).abs() < 1e-9);
+        assert!((proximity.point_b[0] - 3.0).abs() < 1e-9);
+        assert!(
+            (&proximity.point_b - &proximity.point_a - array![2.0, 0.0])
+                .dot(&array![1.0, 1.0])
+                .abs()
+                < 1e-9
+        );
+    }
+
+    #[test]
+    fn test_distance_corner_and_ellipsoid() {
+        let a = Zonotope::from_unit_box(3);
+        let b = Ellipsoid::from_unit_box(3)
+            .translate(&array![3.0, 3.0, 3.0])
+            .unwrap();
+
+        // Corner (1, 1, 1) to the unit ball around (3, 3, 3)
+        let proximity = distance(&a, &b).unwrap();
+        assert!((proximity.distance - (2.0 * 3.0_f64.sqrt() - 1.0)).abs() < 1e-6);
+        assert!(proximity.point_a.abs_diff_eq(&array![1.0, 1.0, 1.0], 1e-6));
+    }
+
+    #[test]
+    fn test_distance_overlapping() {
+        let a = VPolytope::from_unit_box(2);
+        let b = Zonotope::from_unit_box(2)
+            .translate(&array![1.0, 1.5])
+            .unwrap();
+
+        let proximity = distance(&a, &b).unwrap();
+        assert!(proximity.distance < 1e-9);
+        assert!(proximity.point_a.abs_diff_eq(&proximity.point_b, 1e-9));
+    }
+
+    #[test]
+    fn test_penetration_depth() {
+        let a = Interval::from_unit_box(2);
+        let b = Zonotope::from_unit_box(2)
+            .translate(&array![1.5, 0.2])
+            .unwrap();
+
+        let penetration = penetration_depth(&a, &b).unwrap().unwrap();
+        assert!((penetration.depth - 0.5).abs() < 1e-9);
+        assert!(penetration.normal.abs_diff_eq(&array![1.0, 0.0], 1e-9));
+
+        let far_away = b.translate(&array![2.0, 0.0]).unwrap();
+        assert!(penetration_depth(&a, &far_away).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_penetration_depth_ellipsoid() {
+        let a = Ellipsoid::from_unit_box(2);
+        let b = Ellipsoid::from_unit_box(2)
+            .translate(&array![0.0, 1.0])
+            .unwrap();
+
+        let penetration = penetration_depth(&a, &b).unwrap().unwrap();
+        assert!((penetration.depth - 1.0).abs() < 1e-3);
+        assert!(penetration.normal.abs_diff_eq(&array![0.0, 1.0], 1e-2));
+    }
+
+    #[test]
+    fn test_min_norm_point() {
+        // Closest to the edge between the last two points
+        let points = array![[2.0, 3.0], [1.0, -1.0], [-1.0, 1.0], [3.0, 3.0]];
+        let (point, weights) = min_norm_point(&points).unwrap();
+        assert!(point.abs_diff_eq(&array![0.0, 0.0], 1e-9));
+        assert!(weights.abs_diff_eq(&array![0.0, 0.5, 0.5, 0.0], 1e-9));
+
+        let shifted = &points + &array![0.0, 2.0];
+        let (point, _) = min_norm_point(&shifted).unwrap();
+        assert!(point.abs_diff_eq(&array![1.0, 1.0], 1e-9));
+    }
+
+    #[test]
+    fn test_frank_wolfe_closest_point() {
+        let zonotope = Zonotope::from_unit_box(3);
+        let point = array![3.0, -0.5, 2.0];
+        let closest = frank_wolfe_closest_point(&zonotope, &point).unwrap();
+        assert!(closest.abs_diff_eq(&array![1.0, -0.5, 1.0], 1e-9));
+
+        let ellipsoid = Ellipsoid::from_unit_box(2);
+        let closest = frank_wolfe_closest_point(&ellipsoid, &array![3.0, 4.0]).unwrap();
+        assert!(closest.abs_diff_eq(&array![0.6, 0.8], 1e-4));
+    }
+
+    #[test]
+    fn test_distance_not_converged() {
+        let a = Ellipsoid::from_unit_box(2);
+        let b = Ellipsoid::from_unit_box(2)
+            .translate(&array![3.0, 3.0])
+            .unwrap();
+
+        assert!(matches!(
+            gjk(&a, &b, 1),
+            Err(SetOperationError::UnsupportedOperation { .. })
+        ));
+        assert!(gjk(&a, &b, MAX_ITERATIONS).is_ok());
+    }
+
+    #[test]
+    fn test_distance_zero_dimensional() {
+        let a = Interval::from_unit_box(0);
+        assert!(matches!(
+            distance(&a, &a),
+            Err(SetOperationError::UnsupportedOperation { .. })
+        ));
+    }
+}
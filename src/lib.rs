@@ -3,15 +3,26 @@
 
 mod cddlib_rs;
 mod geometric_operations;
+pub mod gjk;
 mod linalg_utils;
 mod qhull_wrapper;
 pub mod sets;
 
-pub use sets::GeoSet;
+pub use sets::affine_hull::AffineHull;
+pub use sets::constrained_zonotope::ConstrainedZonotope;
+pub use sets::containment::{IsSubset, SubsetCheck};
+pub use sets::ellipsoid::Ellipsoid;
+pub use sets::hausdorff::{HausdorffBounds, hausdorff_distance};
 pub use sets::hpolytope::HPolytope;
 pub use sets::interval::Interval;
+pub use sets::lazy::LazySet;
 pub use sets::vpolytope::VPolytope;
 pub use sets::zonotope::Zonotope;
+pub use sets::{GeoSet, SeparatingHyperplane};
 
-pub use cddlib_rs::compute_polytope_vertices;
+pub use cddlib_rs::{
+    PolyhedronGenerators, PolyhedronHalfspaces, compute_polyhedron_generators,
+    compute_polyhedron_generators_with_equalities, compute_polytope_halfspaces,
+    compute_polytope_vertices,
+};
 pub use geometric_operations::order_vertices_clockwise;
@@ -1,4 +1,4 @@
-use ndarray::{Array1, Array2};
+use ndarray::{Array1, Array2, Axis, s};
 use ndarray_linalg::SVD;
 
 pub fn rank(array: &Array2<f64>) -> Result<usize, Box<dyn std::error::Error>> {
@@ -11,6 +11,54 @@ pub fn rank(array: &Array2<f64>) -> Result<usize, Box<dyn std::error::Error>> {
     Ok(rank)
 }
 
+/// Orthonormal basis of the row space of `array`, one basis vector per row.
+/// Taken from the right singular vectors of the non-zero singular values.
+pub fn row_space_basis(array: &Array2<f64>) -> Result<Array2<f64>, Box<dyn std::error::Error>> {
+    if array.is_empty() {
+        return Ok(Array2::zeros((0, array.ncols())));
+    }
+    let (_u, s, vt) = array.svd(false, true)?;
+
+    let tolerance = 1e-10;
+    let rank = s.iter().filter(|&&value| value.abs() > tolerance).count();
+
+    Ok(vt.unwrap().slice(s![..rank, ..]).to_owned())
+}
+
+/// Orthonormal basis of the null space of `array`, one basis vector per row.
+/// Taken from the right singular vectors of the zero singular values.
+pub fn null_space_basis(array: &Array2<f64>) -> Result<Array2<f64>, Box<dyn std::error::Error>> {
+    if array.is_empty() {
+        return Ok(Array2::eye(array.ncols()));
+    }
+    let (_u, s, vt) = array.svd(false, true)?;
+
+    let tolerance = 1e-10;
+    let rank = s.iter().filter(|&&value| value.abs() > tolerance).count();
+
+    Ok(vt.unwrap().slice(s![rank.., ..]).to_owned())
+}
+
+/// Centroid $p$ and orthonormal basis $B$ of the affine hull $\{p + B^\top y\}$ of the
+/// points given as rows
+pub fn affine_basis(
+    points: &Array2<f64>,
+) -> Result<(Array1<f64>, Array2<f64>), Box<dyn std::error::Error>> {
+    let centroid = points
+        .mean_axis(Axis(0))
+        .ok_or("cannot compute the affine hull of no points")?;
+    let basis = row_space_basis(&(points - &centroid))?;
+    Ok((centroid, basis))
+}
+
+/// Block-diagonal matrix $\begin{bmatrix} A & 0 \\ 0 & B \end{bmatrix}$
+pub fn block_diag(a: &Array2<f64>, b: &Array2<f64>) -> Array2<f64> {
+    let mut result = Array2::zeros((a.nrows() + b.nrows(), a.ncols() + b.ncols()));
+    result.slice_mut(s![..a.nrows(), ..a.ncols()]).assign(a);
+    result.slice_mut(s![a.nrows().., a.ncols()..]).assign(b);
+    result
+}
+
 pub fn argmax(vector: &Array1<f64>) -> Option<usize> {
     vector
         .iter()
@@ -19,6 +67,14 @@ pub fn argmax(vector: &Array1<f64>) -> Option<usize> {
         .map(|(i, _)| i)
 }
 
+pub fn argmin(vector: &Array1<f64>) -> Option<usize> {
+    vector
+        .iter()
+        .enumerate()
+        .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+        .map(|(i, _)| i)
+}
+
 pub fn sign(vector: &Array1<f64>) -> Array1<f64> {
     vector.mapv(|x| {
         if x > 0.0 {
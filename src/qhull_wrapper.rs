@@ -3,6 +3,8 @@ use ndarray_linalg::Determinant;
 use qhull::Qh;
 use thiserror::Error;
 
+use crate::linalg_utils::{affine_basis, argmax, argmin};
+
 #[derive(Debug, Error)]
 pub enum ConvexHullError {
     // #[error("Qhull error: {0}")]
@@ -49,36 +51,74 @@ pub fn convex_hull(
         })
 }
 
+/// Computes the vertices of the convex hull of the points given as rows.
+///
+/// Flat point sets are handled inside their affine hull, where qhull sees a full-dimensional
+/// point set. Hulls of relative dimension zero and one are resolved without qhull.
 pub fn convex_hull_vertices(vertices: &Array2<f64>) -> Result<Array2<f64>, ConvexHullError> {
-    let qh = convex_hull(vertices, false)
-        .map_err(|e| ConvexHullError::QhullError {
-            source: Box::new(e),
-        })
-        .unwrap();
+    if vertices.nrows() == 0 {
+        return Ok(Array2::zeros((0, vertices.ncols())));
+    }
+
+    let (centroid, basis) =
+        affine_basis(vertices).map_err(|source| ConvexHullError::DataConversionError { source })?;
+    let local = (vertices - &centroid).dot(&basis.t());
+
+    let indices = match basis.nrows() {
+        0 => vec![0],
+        1 => {
+            let coordinates = local.column(0).to_owned();
+            let mut indices = vec![argmin(&coordinates).unwrap(), argmax(&coordinates).unwrap()];
+            indices.dedup();
+            indices
+        }
+        _ => hull_vertex_indices(&local)?,
+    };
+
+    Ok(vertices.select(Axis(0), &indices))
+}
 
-    // Extract hull vertices
-    let hull_vertices: Vec<Vec<f64>> = qh
-        .vertices()
+/// Row indices of the vertices of the convex hull of a full-dimensional point set
+fn hull_vertex_indices(vertices: &Array2<f64>) -> Result<Vec<usize>, ConvexHullError> {
+    let qh = convex_hull(vertices, false)?;
+
+    qh.vertices()
         .map(|vertex| {
-            let idx = vertex.index(&qh).unwrap();
-            vertices.row(idx).to_vec()
+            vertex
+                .index(&qh)
+                .ok_or_else(|| ConvexHullError::DataConversionError {
+                    source: "Failed to get vertex index".into(),
+                })
         })
-        .collect();
+        .collect()
+}
 
-    // Convert back to Array2
-    if hull_vertices.is_empty() {
-        return Ok(Array2::zeros((0, vertices.ncols())));
+/// Computes the volume of the convex hull of the points given as rows relative to their
+/// affine hull, e.g. the area of a flat polygon in $\mathbb{R}^3$.
+///
+/// A single point has the zero-dimensional volume one.
+pub fn relative_volume(vertices: &Array2<f64>) -> Result<f64, ConvexHullError> {
+    if vertices.nrows() == 0 {
+        return Ok(0.0);
     }
 
-    let n_vertices = hull_vertices.len();
-    let dim = hull_vertices[0].len();
-    let flattened: Vec<f64> = hull_vertices.into_iter().flatten().collect();
-
-    Array2::from_shape_vec((n_vertices, dim), flattened).map_err(|e| {
-        ConvexHullError::DataConversionError {
-            source: Box::new(e),
+    let (centroid, basis) =
+        affine_basis(vertices).map_err(|source| ConvexHullError::DataConversionError { source })?;
+    let local = (vertices - &centroid).dot(&basis.t());
+
+    match basis.nrows() {
+        0 => Ok(1.0),
+        1 => {
+            let coordinates = local.column(0);
+            let max = coordinates.fold(f64::NEG_INFINITY, |acc, &x| acc.max(x));
+            let min = coordinates.fold(f64::INFINITY, |acc, &x| acc.min(x));
+            Ok(max - min)
         }
-    })
+        _ => {
+            let qh = convex_hull(&local, true)?;
+            qhull_volume(&qh, &local)
+        }
+    }
 }
 
 /// Compute the volume of an n-dimensional simplex
@@ -0,0 +1,106 @@
+#![allow(unused)]
+use super::*;
+use crate::linalg_utils::{affine_basis, row_space_basis};
+
+/// Affine subspace $\{p + B^\top y : y \in \mathbb{R}^k\}$ through the point $p$,
+/// spanned by the orthonormal rows of $B \in \mathbb{R}^{k \times n}$.
+#[derive(Clone, Debug)]
+pub struct AffineHull {
+    point: Array1<f64>,
+    basis: Array2<f64>,
+}
+
+impl AffineHull {
+    /// Computes the affine hull of the points given as rows.
+    ///
+    /// The point is the centroid and the basis is taken from the SVD of the centered points.
+    pub fn from_points(points: &Array2<f64>) -> Result<AffineHull, SetOperationError> {
+        if points.nrows() == 0 {
+            return Err(SetOperationError::EmptySet);
+        }
+        let (point, basis) =
+            affine_basis(points).map_err(|e| SetOperationError::DataConversionError {
+                source: e.to_string().into(),
+            })?;
+        Ok(AffineHull { point, basis })
+    }
+
+    /// Affine subspace through `point` spanned by the rows of `directions`
+    pub fn from_directions(
+        point: Array1<f64>,
+        directions: &Array2<f64>,
+    ) -> Result<AffineHull, SetOperationError> {
+        if directions.ncols() != point.dim() {
+            return Err(SetOperationError::DimensionMismatch {
+                expected: point.dim(),
+                got: directions.ncols(),
+            });
+        }
+        let basis =
+            row_space_basis(directions).map_err(|e| SetOperationError::DataConversionError {
+                source: e.to_string().into(),
+            })?;
+        Ok(AffineHull { point, basis })
+    }
+
+    pub fn point(&self) -> &Array1<f64> {
+        &self.point
+    }
+
+    pub fn basis(&self) -> &Array2<f64> {
+        &self.basis
+    }
+
+    /// Dimension $k$ of the subspace
+    pub fn dim(&self) -> usize {
+        self.basis.nrows()
+    }
+
+    /// Dimension $n$ of the surrounding space
+    pub fn ambient_dim(&self) -> usize {
+        self.point.dim()
+    }
+
+    /// Coordinates $B (x - p)$ of the points given as rows
+    pub fn to_local(&self, points: &Array2<f64>) -> Array2<f64> {
+        (points - &self.point).dot(&self.basis.t())
+    }
+
+    /// Points $p + B^\top y$ of the coordinates given as rows
+    pub fn to_global(&self, coordinates: &Array2<f64>) -> Array2<f64> {
+        coordinates.dot(&self.basis) + &self.point
+    }
+
+    /// Checks whether the point lies in the subspace up to `tol`
+    pub fn contains_point(&self, point: &Array1<f64>, tol: f64) -> bool {
+        let offset = point - &self.point;
+        let residual = &offset - &self.basis.t().dot(&self.basis.dot(&offset));
+        residual.dot(&residual).sqrt() <= tol
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_affine_hull_of_flat_points() {
+        let points = array![[0.0, 0.0, 1.0], [1.0, 0.0, 1.0], [0.0, 2.0, 1.0]];
+        let hull = AffineHull::from_points(&points).unwrap();
+
+        assert_eq!(hull.dim(), 2);
+        assert_eq!(hull.ambient_dim(), 3);
+        assert!(hull.contains_point(&array![5.0, -3.0, 1.0], 1e-9));
+        assert!(!hull.contains_point(&array![0.0, 0.0, 0.0], 1e-9));
+
+        let roundtrip = hull.to_global(&hull.to_local(&points));
+        assert!((&roundtrip - &points).iter().all(|x| x.abs() < 1e-9));
+    }
+
+    #[test]
+    fn test_affine_hull_of_single_point() {
+        let hull = AffineHull::from_points(&array![[1.0, 2.0]]).unwrap();
+        assert_eq!(hull.dim(), 0);
+        assert!(hull.contains_point(&array![1.0, 2.0], 1e-12));
+    }
+}
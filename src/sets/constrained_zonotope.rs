@@ -0,0 +1,651 @@
+#![allow(unused)]
+use super::*;
+use crate::cddlib_rs::compute_polytope_vertices;
+use crate::linalg_utils::{block_diag, rank};
+use crate::qhull_wrapper::convex_hull_vertices;
+use crate::sets::zonotope::ZonotopeError;
+use crate::{HPolytope, VPolytope, Zonotope};
+use good_lp::{
+    Constraint, Expression, Solution, SolverModel, Variable, constraint, default_solver, variable,
+    variables,
+};
+use ndarray_linalg::SVD;
+use ndarray_rand::RandomExt;
+use ndarray_rand::rand_distr::{StandardNormal, Uniform};
+use thiserror::Error;
+
+/// Constrained zonotope $\{c + G^\top \xi : A \xi = b, \xi \in [-1, 1]^m\}$.
+/// As for [`Zonotope`], the rows of $G$ are the generators.
+#[derive(Clone, Debug)]
+#[allow(non_snake_case)]
+pub struct ConstrainedZonotope {
+    G: Array2<f64>,
+    c: Array1<f64>,
+    A: Array2<f64>,
+    b: Array1<f64>,
+}
+
+#[derive(Error, Debug)]
+pub enum ConstrainedZonotopeError {
+    #[error("Dimensions of G {g_dim:?} and c {c_dim:?} do not match")]
+    DimensionMismatch { g_dim: (usize, usize), c_dim: usize },
+    #[error("Dimensions of A {a_dim:?} and b {b_dim:?} do not match {n_generators} generators")]
+    ConstraintDimensionMismatch {
+        a_dim: (usize, usize),
+        b_dim: usize,
+        n_generators: usize,
+    },
+    #[error(transparent)]
+    Zonotope(#[from] ZonotopeError),
+}
+
+#[allow(non_snake_case)]
+impl ConstrainedZonotope {
+    pub fn new(
+        G: Array2<f64>,
+        c: Array1<f64>,
+        A: Array2<f64>,
+        b: Array1<f64>,
+    ) -> Result<ConstrainedZonotope, ConstrainedZonotopeError> {
+        if G.dim().1 != c.dim() {
+            return Err(ConstrainedZonotopeError::DimensionMismatch {
+                g_dim: G.dim(),
+                c_dim: c.dim(),
+            });
+        }
+
+        if A.dim() != (b.dim(), G.nrows()) {
+            return Err(ConstrainedZonotopeError::ConstraintDimensionMismatch {
+                a_dim: A.dim(),
+                b_dim: b.dim(),
+                n_generators: G.nrows(),
+            });
+        }
+
+        Ok(ConstrainedZonotope { G, c, A, b })
+    }
+
+    /// Random zonotope with constraints that are satisfied by a random $\xi_0 \in [-0.5, 0.5]^m$
+    pub fn from_random(
+        dim: usize,
+        n_generators: usize,
+        n_constraints: usize,
+    ) -> Result<ConstrainedZonotope, ConstrainedZonotopeError> {
+        let zono = Zonotope::from_random(dim, n_generators, false)?;
+
+        let xi = Array1::random(n_generators, Uniform::new(-0.5, 0.5));
+        let A = Array2::random((n_constraints, n_generators), StandardNormal);
+        let b = A.dot(&xi);
+
+        ConstrainedZonotope::new(zono.G().clone(), zono.c().clone(), A, b)
+    }
+
+    pub fn n_generators(&self) -> usize {
+        self.G.nrows()
+    }
+
+    pub fn n_constraints(&self) -> usize {
+        self.A.nrows()
+    }
+
+    pub fn G(&self) -> &Array2<f64> {
+        &self.G
+    }
+
+    pub fn c(&self) -> &Array1<f64> {
+        &self.c
+    }
+
+    pub fn A(&self) -> &Array2<f64> {
+        &self.A
+    }
+
+    pub fn b(&self) -> &Array1<f64> {
+        &self.b
+    }
+
+    /// Exact intersection with the halfspaces $H x \leq f$.
+    ///
+    /// Each non-redundant halfspace adds one generator and one constraint
+    /// $h^\top (c + G^\top \xi) + \frac{f - \sigma}{2} (1 + \xi_{m+1}) = f$,
+    /// where $\sigma = \min_{x \in Z} h^\top x$ bounds the slack variable.
+    /// Equality constraints $h^\top x = f$ directly add the constraint
+    /// $h^\top G^\top \xi = f - h^\top c$.
+    pub fn intersection_hpolytope_(&mut self, hpoly: &HPolytope) -> Result<(), SetOperationError> {
+        self._check_operand_dim(hpoly.dim())?;
+
+        if hpoly.n_equalities() > 0 {
+            self.A = concatenate![Axis(0), self.A, hpoly.Ae().dot(&self.G.t())];
+            self.b = concatenate![Axis(0), self.b, hpoly.be() - &hpoly.Ae().dot(&self.c)];
+            if self.empty()? {
+                return Err(SetOperationError::EmptySet);
+            }
+        }
+
+        for (h, &f) in hpoly.A().outer_iter().zip(hpoly.b().iter()) {
+            let h = h.to_owned();
+            let (_, upper) = self.support_function(h.clone())?;
+            let (_, lower) = self.support_function(-&h)?;
+            let sigma = -lower;
+
+            if f >= upper {
+                // Redundant halfspace
+                continue;
+            }
+            if f < sigma - 1e-9 {
+                return Err(SetOperationError::EmptySet);
+            }
+
+            let scale = (f - sigma).max(0.0) / 2.;
+            let m = self.n_generators();
+
+            let mut row = Array2::zeros((1, m + 1));
+            row.slice_mut(s![0, ..m]).assign(&self.G.dot(&h));
+            row[[0, m]] = scale;
+
+            self.G = concatenate![Axis(0), self.G, Array2::zeros((1, self.dim()))];
+            self.A = concatenate![
+                Axis(0),
+                concatenate![Axis(1), self.A, Array2::zeros((self.n_constraints(), 1))],
+                row
+            ];
+            self.b = concatenate![Axis(0), self.b, array![f - h.dot(&self.c) - scale]];
+        }
+
+        Ok(())
+    }
+
+    pub fn intersection_hpolytope(
+        &self,
+        hpoly: &HPolytope,
+    ) -> Result<ConstrainedZonotope, SetOperationError> {
+        let mut copy = self.clone();
+        copy.intersection_hpolytope_(hpoly)?;
+        Ok(copy)
+    }
+
+    /// Constraints $A \xi = b$ on the factors `xi`
+    fn factor_constraints(&self, xi: &[Variable]) -> Vec<Constraint> {
+        self.A
+            .outer_iter()
+            .zip(self.b.iter())
+            .map(|(row, &b_i)| {
+                let expr: Expression = row.iter().zip(xi).map(|(a, xi_j)| *a * *xi_j).sum();
+                expr.eq(b_i)
+            })
+            .collect()
+    }
+
+    /// Solves the optimization problem \
+    /// $\min \lambda$ \
+    /// $\text{subject to } c + G^\top \xi = x, A \xi = b, \|\xi\|_\infty \leq \lambda$ \
+    /// If `point` is `None`, the first constraint is dropped.
+    /// Returns `None` if the problem is infeasible.
+    fn min_factor_norm(&self, point: Option<&Array1<f64>>) -> Option<(f64, Array1<f64>)> {
+        let mut vars = variables!();
+        let lambda = vars.add(variable().min(0));
+        let xi: Vec<_> = (0..self.n_generators())
+            .map(|_| vars.add(variable()))
+            .collect();
+
+        let objective: Expression = lambda.into();
+        let mut problem = vars.minimise(objective).using(default_solver);
+
+        if let Some(point) = point {
+            for i in 0..self.dim() {
+                let expr: Expression = self
+                    .G
+                    .column(i)
+                    .iter()
+                    .zip(&xi)
+                    .map(|(g_i, xi_i)| *g_i * *xi_i)
+                    .sum();
+                problem = problem.with(expr.eq(point[i] - self.c[i]));
+            }
+        }
+
+        for constraint in self.factor_constraints(&xi) {
+            problem = problem.with(constraint);
+        }
+
+        for xi_i in &xi {
+            problem = problem.with(constraint!(*xi_i <= lambda));
+            problem = problem.with(constraint!(*xi_i >= -lambda));
+        }
+
+        let solution = problem.solve().ok()?;
+        let xi_val = xi.iter().map(|&xi_i| solution.value(xi_i)).collect();
+        Some((solution.value(lambda), xi_val))
+    }
+}
+
+/// Zonotope without constraints
+impl From<Zonotope> for ConstrainedZonotope {
+    fn from(zono: Zonotope) -> Self {
+        let m = zono.n_generators();
+        ConstrainedZonotope::new(
+            zono.G().clone(),
+            zono.c().clone(),
+            Array2::zeros((0, m)),
+            Array1::zeros(0),
+        )
+        .unwrap()
+    }
+}
+
+#[allow(non_snake_case)]
+impl GeoSet for ConstrainedZonotope {
+    fn from_unit_box(dim: usize) -> Self {
+        Zonotope::from_unit_box(dim).into()
+    }
+
+    fn dim(&self) -> usize {
+        self.c.dim()
+    }
+
+    /// Evaluates the feasibility of $A \xi = b$, $\xi \in [-1, 1]^m$
+    fn empty(&self) -> Result<bool, SetOperationError> {
+        match self.min_factor_norm(None) {
+            Some((lambda, _)) => Ok(lambda > 1.0 + 1e-9),
+            None => Ok(true),
+        }
+    }
+
+    /// Maps the vertices of the factor polytope $\{\xi \in [-1, 1]^m : A \xi = b\}$ and
+    /// removes the points that are not extreme.
+    fn to_vertices(&self) -> Result<Array2<f64>, SetOperationError> {
+        if self.n_constraints() == 0 {
+            return Zonotope::new(self.G.clone(), self.c.clone())
+                .unwrap()
+                .to_vertices();
+        }
+        if self.empty()? {
+            return Err(SetOperationError::EmptySet);
+        }
+
+        let m = self.n_generators();
+        let eye = Array2::<f64>::eye(m);
+        let A = concatenate![Axis(0), self.A, -&self.A, eye, -&eye];
+        let b = concatenate![Axis(0), self.b, -&self.b, Array1::ones(2 * m)];
+
+        let factor_vertices = compute_polytope_vertices(&A, &b)?;
+        let vertices = factor_vertices.dot(&self.G) + &self.c;
+
+        Ok(convex_hull_vertices(&vertices)?)
+    }
+
+    /// Point of the set with the smallest factors $\|\xi\|_\infty$, which is $c$ if
+    /// $\xi = 0$ satisfies the constraints.
+    fn center(&self) -> Result<Array1<f64>, SetOperationError> {
+        if self.n_constraints() == 0 {
+            return Ok(self.c.clone());
+        }
+
+        match self.min_factor_norm(None) {
+            Some((lambda, xi)) if lambda <= 1.0 + 1e-9 => Ok(&self.c + &xi.dot(&self.G)),
+            _ => Err(SetOperationError::EmptySet),
+        }
+    }
+
+    /// Solves the optimization problem: \
+    /// $\max d^\top (c + G^\top \xi)$ \
+    /// $\text{subject to } A \xi = b, \xi \in [-1, 1]^m$ \
+    fn support_function(
+        &self,
+        direction: Array1<f64>,
+    ) -> Result<(Array1<f64>, f64), SetOperationError> {
+        self._check_operand_dim(direction.dim())?;
+
+        if self.n_constraints() == 0 {
+            return Zonotope::new(self.G.clone(), self.c.clone())
+                .unwrap()
+                .support_function(direction);
+        }
+
+        let mut vars = variables!();
+        let xi: Vec<_> = (0..self.n_generators())
+            .map(|_| vars.add(variable().min(-1.0).max(1.0)))
+            .collect();
+
+        let projection = self.G.dot(&direction);
+        let objective: Expression = projection
+            .iter()
+            .zip(&xi)
+            .map(|(p_i, xi_i)| *p_i * *xi_i)
+            .sum();
+        let mut problem = vars.maximise(objective.clone()).using(default_solver);
+
+        for constraint in self.factor_constraints(&xi) {
+            problem = problem.with(constraint);
+        }
+
+        let solution = problem
+            .solve()
+            .map_err(|e| SetOperationError::InfeasibleOptimization {
+                source: Box::new(e),
+            })?;
+
+        let xi_val: Array1<f64> = xi.iter().map(|&xi_i| solution.value(xi_i)).collect();
+        let support_vector = &self.c + &xi_val.dot(&self.G);
+        let support_value = direction.dot(&self.c) + solution.eval(&objective);
+
+        Ok((support_vector, support_value))
+    }
+
+    fn volume(&self) -> Result<f64, SetOperationError> {
+        if self.degenerate() {
+            return Ok(0.0);
+        }
+
+        let vpoly = VPolytope::new(self.to_vertices()?).map_err(|_| SetOperationError::EmptySet)?;
+        vpoly.volume()
+    }
+
+    /// $G = [G_1; G_2]$, $c = c_1 + c_2$ and block-diagonal constraints
+    fn minkowski_sum_(&mut self, other: &Self) -> Result<(), SetOperationError> {
+        self._check_operand_dim(other.dim())?;
+
+        self.A = block_diag(&self.A, &other.A);
+        self.b = concatenate![Axis(0), self.b, other.b];
+        self.G = concatenate![Axis(0), self.G, other.G];
+        self.c = &self.c + &other.c;
+        Ok(())
+    }
+
+    fn matmul_(&mut self, mat: &Array2<f64>) -> Result<(), SetOperationError> {
+        self._check_operand_dim(mat.dim().1)?;
+        self.c = mat.dot(&self.c);
+        self.G = self.G.dot(&mat.t());
+        Ok(())
+    }
+
+    fn translate_(&mut self, vector: &Array1<f64>) -> Result<(), SetOperationError> {
+        self._check_operand_dim(vector.dim())?;
+        self.c = &self.c + vector;
+        Ok(())
+    }
+
+    /// Keeps the generators of `self` and couples the factors of both sets with
+    /// $c_1 + G_1^\top \xi_1 = c_2 + G_2^\top \xi_2$.
+    fn intersection_(&mut self, other: &Self) -> Result<(), SetOperationError> {
+        self._check_operand_dim(other.dim())?;
+
+        let (m1, m2) = (self.n_generators(), other.n_generators());
+        let A = concatenate![
+            Axis(0),
+            block_diag(&self.A, &other.A),
+            concatenate![Axis(1), self.G.t(), -&other.G.t()]
+        ];
+
+        let intersection = ConstrainedZonotope {
+            G: concatenate![Axis(0), self.G, Array2::zeros((m2, self.dim()))],
+            c: self.c.clone(),
+            A,
+            b: concatenate![Axis(0), self.b, other.b, &other.c - &self.c],
+        };
+
+        if intersection.empty()? {
+            return Err(SetOperationError::EmptySet);
+        }
+
+        *self = intersection;
+        Ok(())
+    }
+
+    /// Block-diagonal generators and constraints
+    fn cartesian_product_(&mut self, other: &Self) -> Result<(), SetOperationError> {
+        self.G = block_diag(&self.G, &other.G);
+        self.c = concatenate![Axis(0), self.c, other.c];
+        self.A = block_diag(&self.A, &other.A);
+        self.b = concatenate![Axis(0), self.b, other.b];
+        Ok(())
+    }
+
+    /// Selects the columns of the generators, the constraints are unchanged
+    fn project_(&mut self, dims: &[usize]) -> Result<(), SetOperationError> {
+        self._check_projection_dims(dims)?;
+        self.G = self.G.select(Axis(1), dims);
+        self.c = self.c.select(Axis(0), dims);
+        Ok(())
+    }
+
+    /// Exact hull $\{\lambda x_1 + (1 - \lambda) x_2\}$ with $\hat\lambda = 2\lambda - 1$:
+    /// the factors $\xi_1 = \lambda \xi_1'$, $\xi_2 = (1 - \lambda) \xi_2'$ satisfy \
+    /// $A_1 \xi_1 = \frac{1 + \hat\lambda}{2} b_1$, $A_2 \xi_2 = \frac{1 - \hat\lambda}{2} b_2$,
+    /// $|\xi_1| \leq \frac{1 + \hat\lambda}{2}$, $|\xi_2| \leq \frac{1 - \hat\lambda}{2}$, \
+    /// where each inequality becomes an equality with a slack factor $\sigma \in [-1, 1]$.
+    fn convex_hull_(&mut self, other: &Self) -> Result<(), SetOperationError> {
+        self._check_operand_dim(other.dim())?;
+
+        let (m1, m2) = (self.n_generators(), other.n_generators());
+        let (n1, n2) = (self.n_constraints(), other.n_constraints());
+        let n_slack = 2 * (m1 + m2);
+        let n_factors = m1 + m2 + 1 + n_slack;
+
+        let mut G = Array2::zeros((n_factors, self.dim()));
+        G.slice_mut(s![..m1, ..]).assign(&self.G);
+        G.slice_mut(s![m1..m1 + m2, ..]).assign(&other.G);
+        G.row_mut(m1 + m2).assign(&((&self.c - &other.c) / 2.));
+
+        let mut A = Array2::zeros((n1 + n2 + n_slack, n_factors));
+        let mut b = Array1::zeros(n1 + n2 + n_slack);
+        let lambda = m1 + m2;
+
+        A.slice_mut(s![..n1, ..m1]).assign(&self.A);
+        A.slice_mut(s![..n1, lambda]).assign(&(-&self.b / 2.));
+        b.slice_mut(s![..n1]).assign(&(&self.b / 2.));
+
+        A.slice_mut(s![n1..n1 + n2, m1..m1 + m2]).assign(&other.A);
+        A.slice_mut(s![n1..n1 + n2, lambda])
+            .assign(&(&other.b / 2.));
+        b.slice_mut(s![n1..n1 + n2]).assign(&(&other.b / 2.));
+
+        // One slack factor per bound on each factor of both sets
+        let mut row = n1 + n2;
+        for j in 0..m1 + m2 {
+            let lambda_sign = if j < m1 { -0.5 } else { 0.5 };
+            for sign in [1.0, -1.0] {
+                A[[row, j]] = sign;
+                A[[row, lambda]] = lambda_sign;
+                A[[row, lambda + 1 + row - n1 - n2]] = 1.0;
+                b[row] = -0.5;
+                row += 1;
+            }
+        }
+
+        self.G = G;
+        self.c = (&self.c + &other.c) / 2.;
+        self.A = A;
+        self.b = b;
+        Ok(())
+    }
+
+    /// Computes the exact difference in H-representation and intersects the interval hull of
+    /// the result with it, which represents the difference as constrained zonotope.
+    fn pontryagin_difference_<S: GeoSet>(&mut self, other: &S) -> Result<(), SetOperationError> {
+        self._check_operand_dim(other.dim())?;
+
+        let vertices = VPolytope::new(self.to_vertices()?).map_err(|e| {
+            SetOperationError::DataConversionError {
+                source: Box::new(e),
+            }
+        })?;
+        let difference = vertices.to_hpolytope()?.pontryagin_difference(other)?;
+
+        *self = ConstrainedZonotope::from(Zonotope::from(difference.interval_hull()?));
+        self.intersection_hpolytope_(&difference)
+    }
+
+    /// Checks the rank of the generators restricted to the null space of $A$, i.e., the
+    /// directions in which the factors can move while satisfying the constraints.
+    fn degenerate(&self) -> bool {
+        if self.n_generators() == 0 || self.empty().unwrap_or(true) {
+            return true;
+        }
+        if self.n_constraints() == 0 {
+            return rank(&self.G).unwrap() < self.dim();
+        }
+
+        let (_, s, vt) = self.A.svd(false, true).unwrap();
+        let constraint_rank = s.iter().filter(|&&value| value > 1e-10).count();
+        let null_space = vt.unwrap().slice(s![constraint_rank.., ..]).to_owned();
+
+        let free_generators = null_space.dot(&self.G);
+        free_generators.nrows() == 0 || rank(&free_generators).unwrap() < self.dim()
+    }
+
+    fn contains_point(&self, point: &Array1<f64>) -> Result<bool, SetOperationError> {
+        self._check_operand_dim(point.dim())?;
+
+        match self.min_factor_norm(Some(point)) {
+            Some((lambda, _)) => Ok(lambda <= 1.0 + 1e-9),
+            None => Ok(false),
+        }
+    }
+
+    /// Fully corrective Frank–Wolfe on the support function of the constrained zonotope
+    fn closest_point(&self, point: &Array1<f64>) -> Result<Array1<f64>, SetOperationError> {
+        crate::gjk::frank_wolfe_closest_point(self, point)
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intersection_hpolytope_equality() {
+        let zono = ConstrainedZonotope::from(Zonotope::from_unit_box(2));
+        let line = HPolytope::new_with_equalities(
+            Array2::zeros((0, 2)),
+            Array1::zeros(0),
+            array![[1.0, -1.0]],
+            array![0.0],
+        )
+        .unwrap();
+
+        let diagonal = zono.intersection_hpolytope(&line).unwrap();
+        let (support_vector, _) = diagonal.support_function(array![1.0, 0.0]).unwrap();
+        assert!(support_vector.abs_diff_eq(&array![1.0, 1.0], 1e-9));
+        assert!(!diagonal.contains_point(&array![0.5, -0.5]).unwrap());
+    }
+
+    #[test]
+    fn test_convex_hull_exact() {
+        let first = ConstrainedZonotope::from_random(2, 4, 1).unwrap();
+        let second = ConstrainedZonotope::from_random(2, 3, 1)
+            .unwrap()
+            .translate(&array![2.0, -1.0])
+            .unwrap();
+        let hull = first.convex_hull(&second).unwrap();
+
+        for angle in (0..12).map(|k| k as f64 * std::f64::consts::PI / 6.) {
+            let direction = array![angle.cos(), angle.sin()];
+            let (_, expected_first) = first.support_function(direction.clone()).unwrap();
+            let (_, expected_second) = second.support_function(direction.clone()).unwrap();
+            let (_, value) = hull.support_function(direction).unwrap();
+            assert!((value - expected_first.max(expected_second)).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_constrained_zonotope_new() {
+        let _ = ConstrainedZonotope::new(
+            Array2::eye(2),
+            Array1::zeros(2),
+            array![[1.0, 1.0]],
+            array![0.0],
+        )
+        .unwrap();
+
+        let czono = ConstrainedZonotope::new(
+            Array2::eye(2),
+            Array1::zeros(2),
+            array![[1.0, 1.0, 1.0]],
+            array![0.0],
+        );
+        assert!(czono.is_err());
+    }
+
+    #[test]
+    fn test_constraint_cuts_box() {
+        // Unit box with x + y = 0
+        let czono = ConstrainedZonotope::new(
+            Array2::eye(2),
+            Array1::zeros(2),
+            array![[1.0, 1.0]],
+            array![0.0],
+        )
+        .unwrap();
+
+        assert!(czono.contains_point(&array![0.5, -0.5]).unwrap());
+        assert!(!czono.contains_point(&array![0.5, 0.5]).unwrap());
+        assert!(czono.degenerate());
+
+        let (_, support_value) = czono.support_function(array![1.0, 0.0]).unwrap();
+        assert!((support_value - 1.0).abs() < 1e-9);
+        let (_, support_value) = czono.support_function(array![1.0, 1.0]).unwrap();
+        assert!(support_value.abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_intersection_hpolytope() {
+        let czono = ConstrainedZonotope::from_unit_box(2);
+        // Halfspace x + y <= 1 cuts off the upper right corner
+        let hpoly = HPolytope::new(array![[1.0, 1.0]], array![1.0]).unwrap();
+
+        let intersection = czono.intersection_hpolytope(&hpoly).unwrap();
+        assert_eq!(intersection.n_generators(), 3);
+        assert_eq!(intersection.n_constraints(), 1);
+
+        assert!((intersection.volume().unwrap() - 3.5).abs() < 1e-6);
+        assert!(intersection.contains_point(&array![0.5, 0.5]).unwrap());
+        assert!(!intersection.contains_point(&array![0.6, 0.6]).unwrap());
+
+        let (_, support_value) = intersection.support_function(array![1.0, 1.0]).unwrap();
+        assert!((support_value - 1.0).abs() < 1e-9);
+
+        // Redundant halfspace
+        let hpoly = HPolytope::new(array![[1.0, 0.0]], array![2.0]).unwrap();
+        let intersection = czono.intersection_hpolytope(&hpoly).unwrap();
+        assert_eq!(intersection.n_constraints(), 0);
+
+        // Disjoint halfspace
+        let hpoly = HPolytope::new(array![[1.0, 0.0]], array![-2.0]).unwrap();
+        assert!(matches!(
+            czono.intersection_hpolytope(&hpoly),
+            Err(SetOperationError::EmptySet)
+        ));
+    }
+
+    #[test]
+    fn test_intersection_rotated_boxes() {
+        let czono = ConstrainedZonotope::from_unit_box(2);
+        let s = std::f64::consts::FRAC_1_SQRT_2;
+        let rotated = czono.matmul(&array![[s, -s], [s, s]]).unwrap();
+
+        // Octagon of the unit box and the rotated unit box
+        let intersection = czono.intersection(&rotated).unwrap();
+        assert_eq!(intersection.to_vertices().unwrap().nrows(), 8);
+
+        let expected_volume = 8. * (2.0_f64.sqrt() - 1.);
+        assert!((intersection.volume().unwrap() - expected_volume).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_minkowski_sum_keeps_constraints() {
+        let first = ConstrainedZonotope::from_random(2, 4, 1).unwrap();
+        let second = ConstrainedZonotope::from_random(2, 3, 1).unwrap();
+        let sum = first.minkowski_sum(&second).unwrap();
+
+        assert_eq!(sum.n_generators(), 7);
+        assert_eq!(sum.n_constraints(), 2);
+
+        let direction = array![0.3, -1.0];
+        let (_, value_first) = first.support_function(direction.clone()).unwrap();
+        let (_, value_second) = second.support_function(direction.clone()).unwrap();
+        let (_, value_sum) = sum.support_function(direction).unwrap();
+        assert!((value_sum - value_first - value_second).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,330 @@
+#![allow(unused)]
+use super::*;
+use crate::linalg_utils::argmax;
+use crate::{ConstrainedZonotope, Ellipsoid, HPolytope, VPolytope, Zonotope};
+use good_lp::{Expression, SolverModel, constraint, default_solver, variable, variables};
+use ndarray_linalg::{Eigh, Norm, UPLO};
+
+/// Result of a containment check.
+///
+/// Exact checks always set `exact`. Sufficient checks can only prove containment, so a
+/// negative answer of a sufficient check has `exact == false` and only means that containment
+/// could not be shown.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SubsetCheck {
+    pub is_subset: bool,
+    pub exact: bool,
+}
+
+impl SubsetCheck {
+    fn exact(is_subset: bool) -> Self {
+        SubsetCheck {
+            is_subset,
+            exact: true,
+        }
+    }
+}
+
+/// Containment $\text{self} \subseteq \text{other}$ between set representations.
+pub trait IsSubset<Rhs> {
+    fn is_subset_of(&self, other: &Rhs) -> Result<SubsetCheck, SetOperationError>;
+}
+
+/// $\rho_S(a_i) \leq b_i$ for every row of $Ax \leq b$
+#[allow(non_snake_case)]
+fn subset_of_halfspaces<S: GeoSet>(
+    set: &S,
+    A: &Array2<f64>,
+    b: &Array1<f64>,
+) -> Result<SubsetCheck, SetOperationError> {
+    set._check_operand_dim(A.ncols())?;
+
+    for (row, &b_i) in A.outer_iter().zip(b.iter()) {
+        let (_, support_value) = set.support_function(row.to_owned())?;
+        if support_value > b_i + 1e-9 {
+            return Ok(SubsetCheck::exact(false));
+        }
+    }
+    Ok(SubsetCheck::exact(true))
+}
+
+/// A polytope is contained in a convex set if and only if all its vertices are
+fn subset_by_vertices<R: GeoSet>(
+    vertices: &Array2<f64>,
+    other: &R,
+) -> Result<SubsetCheck, SetOperationError> {
+    other._check_operand_dim(vertices.ncols())?;
+
+    for vertex in vertices.outer_iter() {
+        if !other.contains_point(&vertex.to_owned())? {
+            return Ok(SubsetCheck::exact(false));
+        }
+    }
+    Ok(SubsetCheck::exact(true))
+}
+
+/// Equality constraints $a^\top x = b$ are checked as $a^\top x \leq b$ and $-a^\top x \leq -b$
+#[allow(non_snake_case)]
+impl<T: GeoSet> IsSubset<HPolytope> for T {
+    fn is_subset_of(&self, other: &HPolytope) -> Result<SubsetCheck, SetOperationError> {
+        let A = concatenate![Axis(0), *other.A(), *other.Ae(), -other.Ae()];
+        let b = concatenate![Axis(0), *other.b(), *other.be(), -other.be()];
+        subset_of_halfspaces(self, &A, &b)
+    }
+}
+
+impl<T: GeoSet> IsSubset<Interval> for T {
+    fn is_subset_of(&self, other: &Interval) -> Result<SubsetCheck, SetOperationError> {
+        self._check_operand_dim(other.dim())?;
+        let hull = self.interval_hull()?;
+        let is_subset = hull
+            .lb()
+            .iter()
+            .zip(other.lb().iter())
+            .all(|(a, b)| *a >= b - 1e-9)
+            && hull
+                .ub()
+                .iter()
+                .zip(other.ub().iter())
+                .all(|(a, b)| *a <= b + 1e-9);
+        Ok(SubsetCheck::exact(is_subset))
+    }
+}
+
+/// Checked against the facets and the affine hull of the polytope
+impl<T: GeoSet> IsSubset<VPolytope> for T {
+    fn is_subset_of(&self, other: &VPolytope) -> Result<SubsetCheck, SetOperationError> {
+        self.is_subset_of(&other.to_hpolytope()?)
+    }
+}
+
+/// Checked against the facets and the affine hull of the zonotope
+macro_rules! impl_subset_of_zonotope {
+    ($($set:ty),*) => {
+        $(
+            impl IsSubset<Zonotope> for $set {
+                fn is_subset_of(&self, other: &Zonotope) -> Result<SubsetCheck, SetOperationError> {
+                    self.is_subset_of(&other.to_hpolytope()?)
+                }
+            }
+        )*
+    };
+}
+impl_subset_of_zonotope!(
+    HPolytope,
+    VPolytope,
+    Interval,
+    ConstrainedZonotope,
+    Ellipsoid
+);
+
+/// Checked on the vertices of the polytope
+macro_rules! impl_subset_by_vertices {
+    ($rhs:ty: $($set:ty),*) => {
+        $(
+            impl IsSubset<$rhs> for $set {
+                fn is_subset_of(&self, other: &$rhs) -> Result<SubsetCheck, SetOperationError> {
+                    subset_by_vertices(&self.to_vertices()?, other)
+                }
+            }
+        )*
+    };
+}
+// X ⊆ ConstrainedZonotope and X ⊆ Ellipsoid for the polytopic types X
+impl_subset_by_vertices!(ConstrainedZonotope: HPolytope, VPolytope, Interval, Zonotope, ConstrainedZonotope);
+impl_subset_by_vertices!(Ellipsoid: HPolytope, VPolytope, Interval, Zonotope, ConstrainedZonotope);
+
+/// In the coordinates $y = Q_2^{-1/2} (x - q_2)$ of `other`, `self` is the ellipsoid
+/// $o + M \mathcal{B}$ with $o = Q_2^{-1/2} (q_1 - q_2)$ and $M M^\top = Q_2^{-1/2} Q_1 Q_2^{-1/2}$,
+/// which has to lie in the unit ball. With the largest singular value $\sigma$ of $M$
+/// and its left singular vector $w$, \
+/// $\|o\|_2 + \sigma \leq 1$ proves containment and
+/// $\max(\|o + \sigma w\|_2, \|o - \sigma w\|_2) > 1$ disproves it. \
+/// Both bounds coincide for concentric ellipsoids, otherwise a negative answer is not exact.
+impl IsSubset<Ellipsoid> for Ellipsoid {
+    fn is_subset_of(&self, other: &Ellipsoid) -> Result<SubsetCheck, SetOperationError> {
+        self._check_operand_dim(other.dim())?;
+
+        let inv_sqrt = other.inverse_sqrt_shape()?;
+        let offset = inv_sqrt.dot(&(self.q() - other.q()));
+        let shape = inv_sqrt.dot(self.Q()).dot(&inv_sqrt);
+
+        let (eigenvalues, eigenvectors) =
+            shape
+                .eigh(UPLO::Lower)
+                .map_err(|e| SetOperationError::DataConversionError {
+                    source: Box::new(e),
+                })?;
+        let largest = argmax(&eigenvalues).unwrap();
+        let sigma = eigenvalues[largest].max(0.0).sqrt();
+        let w = eigenvectors.column(largest);
+
+        let tol = 1e-9;
+        if offset.norm_l2() + sigma <= 1.0 + tol {
+            return Ok(SubsetCheck::exact(true));
+        }
+        let farthest = (&offset + &(sigma * &w))
+            .norm_l2()
+            .max((&offset - &(sigma * &w)).norm_l2());
+        if farthest > 1.0 + tol {
+            return Ok(SubsetCheck::exact(false));
+        }
+        Ok(SubsetCheck {
+            is_subset: false,
+            exact: false,
+        })
+    }
+}
+
+/// Sufficient condition for $Z_1 = (c_1, G_1) \subseteq Z_2 = (c_2, G_2)$: the LP \
+/// $G_1^\top = G_2^\top \Gamma$, $c_2 - c_1 = G_2^\top \beta$, $\|[\Gamma, \beta]\|_\infty \leq 1$ \
+/// is feasible, where $\|\cdot\|_\infty$ is the induced matrix norm (maximum absolute row sum).
+///
+/// The condition avoids the facet enumeration of $Z_2$, so a negative answer is not exact.
+#[allow(non_snake_case)]
+impl IsSubset<Zonotope> for Zonotope {
+    fn is_subset_of(&self, other: &Zonotope) -> Result<SubsetCheck, SetOperationError> {
+        self._check_operand_dim(other.dim())?;
+
+        let (m1, m2) = (self.n_generators(), other.n_generators());
+        let mut vars = variables!();
+
+        // Column m1 of Gamma is beta, T bounds the absolute values
+        let Gamma: Vec<Vec<_>> = (0..m2)
+            .map(|_| (0..=m1).map(|_| vars.add(variable())).collect())
+            .collect();
+        let T: Vec<Vec<_>> = (0..m2)
+            .map(|_| (0..=m1).map(|_| vars.add(variable().min(0.0))).collect())
+            .collect();
+
+        let mut problem = vars.minimise(0.0).using(default_solver);
+
+        let target = concatenate![
+            Axis(1),
+            self.G().t(),
+            (other.c() - self.c()).insert_axis(Axis(1))
+        ];
+        for i in 0..self.dim() {
+            for j in 0..=m1 {
+                let expr: Expression = (0..m2).map(|k| other.G()[[k, i]] * Gamma[k][j]).sum();
+                problem = problem.with(expr.eq(target[[i, j]]));
+            }
+        }
+
+        for k in 0..m2 {
+            for j in 0..=m1 {
+                problem = problem.with(constraint!(Gamma[k][j] <= T[k][j]));
+                problem = problem.with(constraint!(Gamma[k][j] >= -T[k][j]));
+            }
+            let row_sum: Expression = T[k].iter().sum();
+            problem = problem.with(row_sum.leq(1.0));
+        }
+
+        match problem.solve() {
+            Ok(_) => Ok(SubsetCheck::exact(true)),
+            Err(_) => Ok(SubsetCheck {
+                is_subset: false,
+                exact: false,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zonotope_subset_lp() {
+        let outer = Zonotope::from_unit_box(2);
+        let inner = Zonotope::new(array![[0.5, 0.0], [0.0, 0.25]], array![0.2, 0.3]).unwrap();
+        assert_eq!(
+            inner.is_subset_of(&outer).unwrap(),
+            SubsetCheck::exact(true)
+        );
+
+        let check = outer.is_subset_of(&inner).unwrap();
+        assert!(!check.is_subset);
+        assert!(!check.exact);
+    }
+
+    #[test]
+    fn test_subset_of_hpolytope() {
+        // Rotated square touching the edges of the outer square
+        let s = 0.5;
+        let inner = Zonotope::new(array![[s, s], [-s, s]], array![0.0, 0.0]).unwrap();
+        let outer = HPolytope::from_unit_box(2);
+        assert_eq!(
+            inner.is_subset_of(&outer).unwrap(),
+            SubsetCheck::exact(true)
+        );
+
+        let shifted = inner.translate(&array![0.1, 0.0]).unwrap();
+        assert_eq!(
+            shifted.is_subset_of(&outer).unwrap(),
+            SubsetCheck::exact(false)
+        );
+
+        let ellipsoid = Ellipsoid::from_unit_box(2);
+        assert_eq!(
+            ellipsoid.is_subset_of(&outer).unwrap(),
+            SubsetCheck::exact(true)
+        );
+        assert_eq!(
+            inner.is_subset_of(&ellipsoid).unwrap(),
+            SubsetCheck::exact(true)
+        );
+    }
+
+    #[test]
+    fn test_ellipsoid_subset_of_ellipsoid() {
+        let outer = Ellipsoid::new(array![0.0, 0.0], array![[4.0, 0.0], [0.0, 1.0]]).unwrap();
+
+        let inner = Ellipsoid::new(array![0.0, 0.0], array![[1.0, 0.0], [0.0, 1.0]]).unwrap();
+        assert_eq!(
+            inner.is_subset_of(&outer).unwrap(),
+            SubsetCheck::exact(true)
+        );
+        assert_eq!(
+            outer.is_subset_of(&inner).unwrap(),
+            SubsetCheck::exact(false)
+        );
+
+        let small = Ellipsoid::new(array![1.0, 0.0], array![[0.25, 0.0], [0.0, 0.25]]).unwrap();
+        assert_eq!(
+            small.is_subset_of(&outer).unwrap(),
+            SubsetCheck::exact(true)
+        );
+        let outside = small.translate(&array![1.5, 0.0]).unwrap();
+        assert_eq!(
+            outside.is_subset_of(&outer).unwrap(),
+            SubsetCheck::exact(false)
+        );
+    }
+
+    #[test]
+    fn test_subset_of_flat_sets() {
+        let square = Interval::new(array![-0.5, -0.5], array![0.5, 0.5]).unwrap();
+        let segment = Interval::new(array![-0.5, 0.0], array![0.5, 0.0]).unwrap();
+
+        let flat_zonotope = Zonotope::new(array![[1.0, 0.0]], array![0.0, 0.0]).unwrap();
+        let flat_vpolytope = VPolytope::new(array![[-1.0, 0.0], [1.0, 0.0]]).unwrap();
+
+        assert_eq!(
+            square.is_subset_of(&flat_zonotope).unwrap(),
+            SubsetCheck::exact(false)
+        );
+        assert_eq!(
+            square.is_subset_of(&flat_vpolytope).unwrap(),
+            SubsetCheck::exact(false)
+        );
+        assert_eq!(
+            segment.is_subset_of(&flat_zonotope).unwrap(),
+            SubsetCheck::exact(true)
+        );
+        assert_eq!(
+            segment.is_subset_of(&flat_vpolytope).unwrap(),
+            SubsetCheck::exact(true)
+        );
+    }
+}
@@ -0,0 +1,507 @@
+#![allow(unused)]
+use super::*;
+use crate::linalg_utils::{block_diag, rank};
+use ndarray_linalg::{Determinant, Eigh, UPLO};
+use ndarray_rand::RandomExt;
+use ndarray_rand::rand_distr::Uniform;
+use std::f64::consts::PI;
+use thiserror::Error;
+
+/// Number of boundary points sampled per coordinate plane
+const N_BOUNDARY_SAMPLES: usize = 64;
+
+/// Ellipsoid $\{x : (x - q)^T Q^{-1} (x - q) \leq 1\}$ with center $q$ and symmetric positive
+/// semi-definite shape matrix $Q$.
+/// A singular $Q$ describes a degenerate ellipsoid $\{q + Q^{1/2} u : \|u\|_2 \leq 1\}$.
+#[derive(Clone, Debug)]
+#[allow(non_snake_case)]
+pub struct Ellipsoid {
+    q: Array1<f64>,
+    Q: Array2<f64>,
+}
+
+#[derive(Error, Debug)]
+pub enum EllipsoidError {
+    #[error("Dimensions of Q {shape_dim:?} and q {q_dim:?} do not match")]
+    DimensionMismatch {
+        shape_dim: (usize, usize),
+        q_dim: usize,
+    },
+    #[error("Shape matrix Q must be symmetric")]
+    NotSymmetric,
+    #[error("Shape matrix Q must be positive semi-definite, smallest eigenvalue is {eigenvalue}")]
+    NotPositiveSemiDefinite { eigenvalue: f64 },
+}
+
+#[allow(non_snake_case)]
+impl Ellipsoid {
+    pub fn new(q: Array1<f64>, Q: Array2<f64>) -> Result<Ellipsoid, EllipsoidError> {
+        if Q.nrows() != Q.ncols() || Q.nrows() != q.dim() {
+            return Err(EllipsoidError::DimensionMismatch {
+                shape_dim: Q.dim(),
+                q_dim: q.dim(),
+            });
+        }
+
+        if !Q.abs_diff_eq(&Q.t(), 1e-9) {
+            return Err(EllipsoidError::NotSymmetric);
+        }
+
+        if q.dim() > 0 {
+            let (eigenvalues, _) = Q.eigh(UPLO::Lower).unwrap();
+            let min_eigenvalue = eigenvalues.fold(f64::INFINITY, |a, &b| a.min(b));
+            if min_eigenvalue < -1e-9 {
+                return Err(EllipsoidError::NotPositiveSemiDefinite {
+                    eigenvalue: min_eigenvalue,
+                });
+            }
+        }
+
+        Ok(Ellipsoid { q, Q })
+    }
+
+    pub fn from_random(dim: usize) -> Result<Ellipsoid, EllipsoidError> {
+        let A = Array2::random((dim, dim), Uniform::new(-1.0, 1.0));
+        let Q = A.dot(&A.t()) / dim as f64 + 0.01 * Array2::<f64>::eye(dim);
+        let q = Array1::random(dim, Uniform::new(-0.2, 0.2));
+
+        // Symmetrize to remove round-off from the product
+        let Q = (&Q + &Q.t()) / 2.;
+        Ellipsoid::new(q, Q)
+    }
+
+    pub fn q(&self) -> &Array1<f64> {
+        &self.q
+    }
+
+    pub fn Q(&self) -> &Array2<f64> {
+        &self.Q
+    }
+
+    /// Inverse square root $Q^{-1/2}$ of the shape matrix.
+    /// Degenerate ellipsoids are not supported.
+    pub(crate) fn inverse_sqrt_shape(&self) -> Result<Array2<f64>, SetOperationError> {
+        let (eigenvalues, eigenvectors) =
+            self.Q
+                .eigh(UPLO::Lower)
+                .map_err(|e| SetOperationError::DataConversionError {
+                    source: Box::new(e),
+                })?;
+        if eigenvalues.iter().any(|&value| value <= 1e-12) {
+            return Err(SetOperationError::UnsupportedOperation {
+                message: "the shape matrix must be positive definite".to_string(),
+            });
+        }
+        Ok(eigenvectors
+            .dot(&Array2::from_diag(
+                &eigenvalues.mapv(|value| 1. / value.sqrt()),
+            ))
+            .dot(&eigenvectors.t()))
+    }
+
+    /// Support points along directions in the plane spanned by $e_i$ and $e_j$, ordered by angle.
+    fn boundary_points(&self, i: usize, j: usize) -> Result<Array2<f64>, SetOperationError> {
+        let mut points = Array2::zeros((N_BOUNDARY_SAMPLES, self.dim()));
+        for k in 0..N_BOUNDARY_SAMPLES {
+            let angle = 2. * PI * k as f64 / N_BOUNDARY_SAMPLES as f64;
+            let mut direction = Array1::zeros(self.dim());
+            direction[i] = angle.cos();
+            direction[j] = angle.sin();
+
+            let (support_vector, _) = self.support_function(direction)?;
+            points.row_mut(k).assign(&support_vector);
+        }
+        Ok(points)
+    }
+}
+
+#[allow(non_snake_case)]
+impl GeoSet for Ellipsoid {
+    /// Unit ball, i.e., the largest ellipsoid inscribed in the unit box.
+    fn from_unit_box(dim: usize) -> Self {
+        Ellipsoid::new(Array1::zeros(dim), Array2::eye(dim)).unwrap()
+    }
+
+    fn dim(&self) -> usize {
+        self.q.dim()
+    }
+
+    fn empty(&self) -> Result<bool, SetOperationError> {
+        Ok(false)
+    }
+
+    /// Ellipsoids have no vertices, instead boundary points sampled in every coordinate plane
+    /// are returned. Their convex hull is an inner approximation of the ellipsoid.
+    fn to_vertices(&self) -> Result<Array2<f64>, SetOperationError> {
+        if self.dim() == 1 {
+            let radius = self.Q[[0, 0]].sqrt();
+            return Ok(array![[self.q[0] - radius], [self.q[0] + radius]]);
+        }
+
+        let mut points = Array2::zeros((0, self.dim()));
+        for i in 0..self.dim() {
+            for j in (i + 1)..self.dim() {
+                points = concatenate![Axis(0), points, self.boundary_points(i, j)?];
+            }
+        }
+        Ok(points)
+    }
+
+    fn center(&self) -> Result<Array1<f64>, SetOperationError> {
+        Ok(self.q.clone())
+    }
+
+    /// $\rho(d) = d^T q + \sqrt{d^T Q d}$, attained at $q + Q d / \sqrt{d^T Q d}$
+    fn support_function(
+        &self,
+        direction: Array1<f64>,
+    ) -> Result<(Array1<f64>, f64), SetOperationError> {
+        self._check_operand_dim(direction.dim())?;
+
+        let Qd = self.Q.dot(&direction);
+        let radius = direction.dot(&Qd).max(0.0).sqrt();
+
+        let support_value = direction.dot(&self.q) + radius;
+        let support_vector = if radius > 1e-12 {
+            &self.q + &(Qd / radius)
+        } else {
+            self.q.clone()
+        };
+
+        Ok((support_vector, support_value))
+    }
+
+    /// Volume of the unit ball scaled by $\sqrt{\det Q}$
+    fn volume(&self) -> Result<f64, SetOperationError> {
+        if self.degenerate() {
+            return Ok(0.0);
+        }
+
+        // V_n = 2 \pi / n V_{n-2} with V_0 = 1 and V_1 = 2
+        let n = self.dim();
+        let mut unit_ball_volume = if n.is_multiple_of(2) { 1.0 } else { 2.0 };
+        for k in ((2 + n % 2)..=n).step_by(2) {
+            unit_ball_volume *= 2. * PI / k as f64;
+        }
+
+        let det = self
+            .Q
+            .det()
+            .map_err(|e| SetOperationError::DataConversionError {
+                source: Box::new(e),
+            })?;
+        Ok(unit_ball_volume * det.sqrt())
+    }
+
+    /// Computes a trace-minimal outer approximation
+    /// $Q = (1 + 1/p) Q_1 + (1 + p) Q_2$ with $p = \sqrt{\mathrm{tr}(Q_1) / \mathrm{tr}(Q_2)}$,
+    /// which is exact if one of the operands is a point.
+    fn minkowski_sum_(&mut self, other: &Self) -> Result<(), SetOperationError> {
+        self._check_operand_dim(other.dim())?;
+
+        let trace_self = self.Q.diag().sum();
+        let trace_other = other.Q.diag().sum();
+
+        if trace_other <= 1e-12 {
+            // Nothing to do
+        } else if trace_self <= 1e-12 {
+            self.Q = other.Q.clone();
+        } else {
+            let p = (trace_self / trace_other).sqrt();
+            self.Q = (1. + 1. / p) * &self.Q + (1. + p) * &other.Q;
+        }
+
+        self.q = &self.q + &other.q;
+        Ok(())
+    }
+
+    fn matmul_(&mut self, mat: &Array2<f64>) -> Result<(), SetOperationError> {
+        self._check_operand_dim(mat.dim().1)?;
+        self.q = mat.dot(&self.q);
+        self.Q = mat.dot(&self.Q).dot(&mat.t());
+        Ok(())
+    }
+
+    fn translate_(&mut self, vector: &Array1<f64>) -> Result<(), SetOperationError> {
+        self._check_operand_dim(vector.dim())?;
+        self.q = &self.q + vector;
+        Ok(())
+    }
+
+    fn intersection_(&mut self, other: &Self) -> Result<(), SetOperationError> {
+        Err(SetOperationError::UnsupportedOperation {
+            message: "ellipsoids are not closed under intersection".to_string(),
+        })
+    }
+
+    /// Computes the outer approximation $Q = \mathrm{diag}(Q_1 / \lambda, Q_2 / (1 - \lambda))$
+    /// with the volume-minimal $\lambda = n_1 / (n_1 + n_2)$.
+    fn cartesian_product_(&mut self, other: &Self) -> Result<(), SetOperationError> {
+        let (n1, n2) = (self.dim() as f64, other.dim() as f64);
+        if n1 == 0.0 || n2 == 0.0 {
+            self.Q = block_diag(&self.Q, &other.Q);
+        } else {
+            let lambda = n1 / (n1 + n2);
+            self.Q = block_diag(&(&self.Q / lambda), &(&other.Q / (1. - lambda)));
+        }
+        self.q = concatenate![Axis(0), self.q, other.q];
+        Ok(())
+    }
+
+    /// $q_{dims}$ and $Q_{dims, dims}$
+    fn project_(&mut self, dims: &[usize]) -> Result<(), SetOperationError> {
+        self._check_projection_dims(dims)?;
+        self.q = self.q.select(Axis(0), dims);
+        self.Q = self.Q.select(Axis(0), dims).select(Axis(1), dims);
+        Ok(())
+    }
+
+    fn convex_hull_(&mut self, other: &Self) -> Result<(), SetOperationError> {
+        Err(SetOperationError::NotImplemented)
+    }
+
+    /// Computes the inner approximation $\mathcal{E}(q - c, \alpha^2 Q)$ with the center $c$ of
+    /// `other` and $\alpha = 1 - r$, where $r$ bounds the radius of `other` in the metric of $Q$: \
+    /// $r \geq \max_{x \in other} \|Q^{-1/2} (x - c)\|_2$ \
+    /// The bound is the interval hull of $Q^{-1/2} (other - c)$ from the support function of
+    /// `other` along $\pm Q^{-1/2} e_i$, so the shrunk ellipsoid plus `other` stays inside.
+    /// Returns [`SetOperationError::EmptySet`] if the approximation is empty.
+    fn pontryagin_difference_<S: GeoSet>(&mut self, other: &S) -> Result<(), SetOperationError> {
+        self._check_operand_dim(other.dim())?;
+
+        let inv_sqrt = self.inverse_sqrt_shape()?;
+
+        // Q^{-1/2} is symmetric, so its rows are Q^{-1/2} e_i
+        let center = other.center()?;
+        let mut radius_squared = 0.0;
+        for direction in inv_sqrt.outer_iter() {
+            let (_, upper) = other.support_function(direction.to_owned())?;
+            let (_, lower) = other.support_function(-&direction)?;
+            let offset = direction.dot(&center);
+            radius_squared += (upper - offset).max(lower + offset).powi(2);
+        }
+
+        let alpha = 1. - radius_squared.sqrt();
+        if alpha < 0. {
+            return Err(SetOperationError::EmptySet);
+        }
+        self.q -= &center;
+        self.Q *= alpha.powi(2);
+        Ok(())
+    }
+
+    /// Center $q$ and the range of the shape matrix $Q$
+    fn affine_hull(&self) -> Result<AffineHull, SetOperationError> {
+        AffineHull::from_directions(self.q.clone(), &self.Q)
+    }
+
+    fn degenerate(&self) -> bool {
+        self.dim() == 0 || rank(&self.Q).unwrap() < self.dim()
+    }
+
+    /// $q \pm \sqrt{\mathrm{diag}(Q)}$
+    fn interval_hull(&self) -> Result<Interval, SetOperationError> {
+        let radius = self.Q.diag().mapv(|x| x.max(0.0).sqrt());
+        Interval::new(&self.q - &radius, &self.q + &radius).map_err(|e| {
+            SetOperationError::DataConversionError {
+                source: Box::new(e),
+            }
+        })
+    }
+
+    /// Evaluates $(x - q)^T Q^{-1} (x - q) \leq 1$ in the eigenbasis of $Q$, so that degenerate
+    /// ellipsoids only contain points without components along the zero eigenvalues.
+    fn contains_point(&self, point: &Array1<f64>) -> Result<bool, SetOperationError> {
+        self._check_operand_dim(point.dim())?;
+
+        let (eigenvalues, eigenvectors) =
+            self.Q
+                .eigh(UPLO::Lower)
+                .map_err(|e| SetOperationError::DataConversionError {
+                    source: Box::new(e),
+                })?;
+        let y = eigenvectors.t().dot(&(point - &self.q));
+
+        let mut value = 0.0;
+        for (&lambda, &y_i) in eigenvalues.iter().zip(y.iter()) {
+            if lambda > 1e-9 {
+                value += y_i * y_i / lambda;
+            } else if y_i.abs() > 1e-9 {
+                return Ok(false);
+            }
+        }
+        Ok(value <= 1.0 + 1e-9)
+    }
+
+    /// Plots the boundary of the projected ellipse instead of the convex hull of vertices.
+    fn create_trace(
+        &self,
+        dim: (usize, usize),
+        name: Option<&str>,
+    ) -> Result<Box<dyn Trace>, SetOperationError> {
+        let points = self.boundary_points(dim.0, dim.1)?;
+
+        let mut x = points.column(dim.0).to_vec();
+        let mut y = points.column(dim.1).to_vec();
+        x.push(x[0]);
+        y.push(y[0]);
+
+        let mut trace = Scatter::new(x, y)
+            .mode(Mode::Lines)
+            .fill(plotly::common::Fill::ToSelf)
+            .opacity(0.8);
+
+        if let Some(trace_name) = name {
+            trace = trace.name(trace_name);
+        }
+
+        Ok(trace)
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ellipsoid_new() {
+        let _ = Ellipsoid::new(Array1::zeros(2), Array2::eye(2)).unwrap();
+        let _ = Ellipsoid::new(Array1::zeros(2), array![[1.0, 0.0], [0.0, 0.0]]).unwrap();
+
+        assert!(Ellipsoid::new(Array1::zeros(3), Array2::eye(2)).is_err());
+        assert!(Ellipsoid::new(Array1::zeros(2), array![[1.0, 1.0], [0.0, 1.0]]).is_err());
+        assert!(Ellipsoid::new(Array1::zeros(2), array![[1.0, 0.0], [0.0, -1.0]]).is_err());
+    }
+
+    #[test]
+    fn test_pontryagin_difference_inner_approximation() {
+        let disk = Ellipsoid::from_unit_box(2);
+        let square = Interval::new(array![-0.1, 0.2], array![0.1, 0.4]).unwrap();
+
+        let difference = disk.pontryagin_difference(&square).unwrap();
+        assert!(difference.q().abs_diff_eq(&array![0.0, -0.3], 1e-9));
+
+        // The difference plus the square stays inside the disk
+        for direction in hausdorff::sample_directions(2, 32).outer_iter() {
+            let (_, inner) = difference.support_function(direction.to_owned()).unwrap();
+            let (_, offset) = square.support_function(direction.to_owned()).unwrap();
+            let (_, outer) = disk.support_function(direction.to_owned()).unwrap();
+            assert!(inner + offset <= outer + 1e-9);
+        }
+
+        let large = Interval::from_unit_box(2);
+        assert!(matches!(
+            disk.pontryagin_difference(&large),
+            Err(SetOperationError::EmptySet)
+        ));
+    }
+
+    #[test]
+    fn test_support_function() {
+        let ellipsoid = Ellipsoid::new(array![1.0, 0.0], array![[4.0, 0.0], [0.0, 1.0]]).unwrap();
+
+        let (support_vector, support_value) = ellipsoid.support_function(array![1.0, 0.0]).unwrap();
+        assert!(support_vector.abs_diff_eq(&array![3.0, 0.0], 1e-10));
+        assert!((support_value - 3.0).abs() < 1e-10);
+
+        let (_, support_value) = ellipsoid.support_function(array![0.0, -1.0]).unwrap();
+        assert!((support_value - 1.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_volume() {
+        let disk = Ellipsoid::from_unit_box(2);
+        assert!((disk.volume().unwrap() - PI).abs() < 1e-10);
+
+        let ball = Ellipsoid::from_unit_box(3);
+        assert!((ball.volume().unwrap() - 4. / 3. * PI).abs() < 1e-10);
+
+        let ellipse = Ellipsoid::new(Array1::zeros(2), array![[4.0, 0.0], [0.0, 9.0]]).unwrap();
+        assert!((ellipse.volume().unwrap() - 6. * PI).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_contains_point() {
+        let ellipse = Ellipsoid::new(array![1.0, 1.0], array![[4.0, 0.0], [0.0, 1.0]]).unwrap();
+        assert!(ellipse.contains_point(&array![2.9, 1.0]).unwrap());
+        assert!(ellipse.contains_point(&array![1.0, 2.0]).unwrap());
+        assert!(!ellipse.contains_point(&array![2.5, 1.9]).unwrap());
+
+        // Line segment from (-1, 0) to (1, 0)
+        let segment = Ellipsoid::new(Array1::zeros(2), array![[1.0, 0.0], [0.0, 0.0]]).unwrap();
+        assert!(segment.contains_point(&array![0.5, 0.0]).unwrap());
+        assert!(!segment.contains_point(&array![0.5, 0.1]).unwrap());
+    }
+
+    #[test]
+    fn test_matmul() {
+        let mut ellipse = Ellipsoid::new(array![1.0, 2.0], Array2::eye(2)).unwrap();
+        ellipse.matmul_(&array![[2.0, 0.0], [0.0, 3.0]]).unwrap();
+
+        assert!(ellipse.q.abs_diff_eq(&array![2.0, 6.0], 1e-10));
+        assert!(
+            ellipse
+                .Q
+                .abs_diff_eq(&array![[4.0, 0.0], [0.0, 9.0]], 1e-10)
+        );
+    }
+
+    #[test]
+    fn test_minkowski_sum_outer_approximation() {
+        let first = Ellipsoid::from_random(3).unwrap();
+        let second = Ellipsoid::from_random(3).unwrap();
+        let sum = first.minkowski_sum(&second).unwrap();
+
+        for _ in 0..20 {
+            let direction = Array1::random(3, Uniform::new(-1.0, 1.0));
+            let (_, value_first) = first.support_function(direction.clone()).unwrap();
+            let (_, value_second) = second.support_function(direction.clone()).unwrap();
+            let (_, value_sum) = sum.support_function(direction).unwrap();
+            assert!(value_sum >= value_first + value_second - 1e-9);
+        }
+
+        // Exact for balls with the same shape
+        let ball = Ellipsoid::from_unit_box(2);
+        let sum = ball.minkowski_sum(&ball).unwrap();
+        assert!(sum.Q.abs_diff_eq(&(4.0 * Array2::<f64>::eye(2)), 1e-10));
+    }
+
+    #[test]
+    fn test_cartesian_product_outer_approximation() {
+        let first = Ellipsoid::from_random(2).unwrap();
+        let second = Ellipsoid::from_random(1).unwrap();
+        let product = first.cartesian_product(&second).unwrap();
+        assert_eq!(product.dim(), 3);
+
+        for v in first.to_vertices().unwrap().outer_iter() {
+            for w in second.to_vertices().unwrap().outer_iter() {
+                let point = concatenate![Axis(0), v, w];
+                assert!(product.contains_point(&point).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_interval_hull() {
+        let ellipsoid = Ellipsoid::from_random(3).unwrap();
+        let hull = ellipsoid.interval_hull().unwrap();
+
+        for i in 0..3 {
+            let mut direction = Array1::zeros(3);
+            direction[i] = 1.0;
+            let (_, upper) = ellipsoid.support_function(direction.clone()).unwrap();
+            let (_, lower) = ellipsoid.support_function(-direction).unwrap();
+            assert!((hull.ub()[i] - upper).abs() < 1e-10);
+            assert!((hull.lb()[i] + lower).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_boundary_points_contained() {
+        let ellipsoid = Ellipsoid::from_random(3).unwrap();
+        for point in ellipsoid.to_vertices().unwrap().outer_iter() {
+            assert!(ellipsoid.contains_point(&point.to_owned()).unwrap());
+        }
+    }
+}
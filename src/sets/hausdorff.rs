@@ -0,0 +1,183 @@
+#![allow(unused)]
+use super::*;
+use crate::{HPolytope, VPolytope};
+use ndarray_rand::RandomExt;
+use ndarray_rand::rand_distr::StandardNormal;
+
+/// Lower and upper bound on the Hausdorff distance of two sets.
+/// Both bounds coincide if the distance was computed exactly.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HausdorffBounds {
+    pub lower: f64,
+    pub upper: f64,
+}
+
+impl HausdorffBounds {
+    pub fn is_exact(&self, tol: f64) -> bool {
+        self.upper - self.lower <= tol
+    }
+}
+
+/// Samples `n` directions uniformly from the unit sphere, one per row.
+pub fn sample_directions(dim: usize, n: usize) -> Array2<f64> {
+    let mut directions: Array2<f64> = Array2::random((n, dim), StandardNormal);
+    for mut row in directions.rows_mut() {
+        let norm = row.dot(&row).sqrt();
+        if norm > 0.0 {
+            row /= norm;
+        }
+    }
+    directions
+}
+
+/// Bounds the Hausdorff distance
+/// $d_H(A, B) = \max \{ \max_{x \in A} d(x, B), \max_{y \in B} d(y, A) \}$
+/// of two convex sets using their support functions along the rows of `directions`.
+///
+/// The lower bound is the largest of $|\rho_A(d) - \rho_B(d)|$ and the distances of the
+/// support vectors of one set to the other set. The upper bound is the largest distance of a
+/// vertex of the template outer approximation $\{x : d_i^\top x \leq \rho_A(d_i)\}$ to the
+/// other set, as the distance to a convex set attains its maximum over a polytope at a vertex.
+/// The directions $\pm e_i$ are always added so that the template is bounded.
+/// More directions tighten both bounds.
+pub fn hausdorff_distance<A: GeoSet, B: GeoSet>(
+    a: &A,
+    b: &B,
+    directions: &Array2<f64>,
+) -> Result<HausdorffBounds, SetOperationError> {
+    a._check_operand_dim(b.dim())?;
+    a._check_operand_dim(directions.ncols())?;
+
+    let dim = a.dim();
+    let directions = concatenate![
+        Axis(0),
+        directions.view(),
+        Array2::eye(dim).view(),
+        (-Array2::<f64>::eye(dim)).view()
+    ];
+
+    let (lower_a, outer_a) = one_sided_bounds(a, b, &directions)?;
+    let (lower_b, outer_b) = one_sided_bounds(b, a, &directions)?;
+
+    let mut lower = lower_a.max(lower_b);
+    for ((direction, rho_a), rho_b) in directions
+        .outer_iter()
+        .zip(outer_a.b().iter())
+        .zip(outer_b.b().iter())
+    {
+        let norm = direction.dot(&direction).sqrt();
+        if norm > 0.0 {
+            lower = lower.max((rho_a - rho_b).abs() / norm);
+        }
+    }
+
+    let upper = max_vertex_distance(&outer_a, b)?.max(max_vertex_distance(&outer_b, a)?);
+
+    Ok(HausdorffBounds {
+        lower,
+        upper: upper.max(lower),
+    })
+}
+
+/// Largest distance of a vertex of the polytope to the set
+fn max_vertex_distance<S: GeoSet>(polytope: &HPolytope, set: &S) -> Result<f64, SetOperationError> {
+    let mut distance: f64 = 0.0;
+    for vertex in polytope.to_vertices()?.outer_iter() {
+        distance = distance.max(set.distance_to_point(&vertex.to_owned())?);
+    }
+    Ok(distance)
+}
+
+/// Lower bound on $\max_{x \in A} d(x, B)$ from the support vectors of $A$ and the template
+/// outer approximation of $A$
+fn one_sided_bounds<A: GeoSet, B: GeoSet>(
+    a: &A,
+    b: &B,
+    directions: &Array2<f64>,
+) -> Result<(f64, HPolytope), SetOperationError> {
+    let mut lower: f64 = 0.0;
+    let mut support_values = Array1::zeros(directions.nrows());
+
+    for (direction, value) in directions.outer_iter().zip(support_values.iter_mut()) {
+        let (support_vector, support_value) = a.support_function(direction.to_owned())?;
+        lower = lower.max(b.distance_to_point(&support_vector)?);
+        *value = support_value;
+    }
+
+    let outer = HPolytope::new(directions.clone(), support_values).map_err(|e| {
+        SetOperationError::DataConversionError {
+            source: Box::new(e),
+        }
+    })?;
+    Ok((lower, outer))
+}
+
+impl VPolytope {
+    /// Computes the exact Hausdorff distance to another polytope.
+    ///
+    /// The distance to a convex set attains its maximum over a polytope at a vertex, so only
+    /// the distances of the vertices of each polytope to the other one are needed.
+    pub fn hausdorff_distance(&self, other: &VPolytope) -> Result<f64, SetOperationError> {
+        self._check_operand_dim(other.dim())?;
+
+        let mut distance: f64 = 0.0;
+        for (from, to) in [(self, other), (other, self)] {
+            for vertex in from.vertices().outer_iter() {
+                distance = distance.max(to.distance_to_point(&vertex.to_owned())?);
+            }
+        }
+        Ok(distance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Interval, Zonotope};
+    use zonotope::ReductionMethod;
+
+    #[test]
+    fn test_vpolytope_hausdorff_distance() {
+        let inner = VPolytope::from_unit_box(2);
+        let outer = inner.matmul(&(2.0 * Array2::eye(2))).unwrap();
+
+        let distance = inner.hausdorff_distance(&outer).unwrap();
+        assert!((distance - 2.0_f64.sqrt()).abs() < 1e-9);
+        assert!((outer.hausdorff_distance(&inner).unwrap() - distance).abs() < 1e-12);
+        assert!(inner.hausdorff_distance(&inner).unwrap() < 1e-9);
+    }
+
+    #[test]
+    fn test_hausdorff_bounds_boxes() {
+        let a = Interval::from_unit_box(2);
+        let b = Zonotope::from_unit_box(2)
+            .translate(&array![0.5, 0.0])
+            .unwrap();
+
+        // The box template describes both sets exactly
+        let bounds = hausdorff_distance(&a, &b, &Array2::zeros((0, 2))).unwrap();
+        assert!((bounds.lower - 0.5).abs() < 1e-6);
+        assert!(bounds.is_exact(1e-6));
+    }
+
+    #[test]
+    fn test_hausdorff_bounds_enclose_exact_distance() {
+        let a = VPolytope::from_random(2, 6).unwrap();
+        let b = VPolytope::from_random(2, 6).unwrap();
+        let exact = a.hausdorff_distance(&b).unwrap();
+
+        let bounds = hausdorff_distance(&a, &b, &sample_directions(2, 16)).unwrap();
+        assert!(bounds.lower <= exact + 1e-6);
+        assert!(exact <= bounds.upper + 1e-6);
+    }
+
+    #[test]
+    fn test_hausdorff_bounds_reduced_zonotope() {
+        let zonotope = Zonotope::from_random(2, 8, false).unwrap();
+        let reduced = zonotope.reduce_order(2, ReductionMethod::Girard).unwrap();
+
+        let bounds = hausdorff_distance(&zonotope, &reduced, &sample_directions(2, 32)).unwrap();
+        assert!(bounds.lower <= bounds.upper + 1e-9);
+        assert!(bounds.upper > 0.0);
+    }
+}
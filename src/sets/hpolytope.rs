@@ -1,26 +1,44 @@
 #![allow(unused)]
 use super::*;
 use crate::VPolytope;
-use crate::cddlib_rs::compute_polytope_vertices;
-use crate::linalg_utils::{rank, vector_leq};
-use good_lp::{Expression, Solution, SolverModel, default_solver, variable, variables};
-use ndarray_linalg::Norm;
+use crate::cddlib_rs::{PolyhedronGenerators, compute_polyhedron_generators_with_equalities};
+use crate::linalg_utils::{
+    argmin, block_diag, null_space_basis, rank, row_space_basis, vector_all_close, vector_leq,
+};
+use good_lp::{
+    Constraint, Expression, ResolutionError, Solution, SolverModel, Variable, default_solver,
+    variable, variables,
+};
+use itertools::Itertools;
+use ndarray_linalg::{Norm, SVD, Solve};
 use ndarray_rand::RandomExt;
 use ndarray_rand::rand_distr::{Normal, StandardNormal, Uniform};
 use plotly::box_plot;
 use thiserror::Error;
 
+/// Maximum number of working set changes in [`GeoSet::closest_point`]
+const MAX_ACTIVE_SET_ITERATIONS: usize = 1000;
+
+/// Polyhedron $\{x : Ax \leq b, A_e x = b_e\}$. The equality constraints are optional.
 #[derive(Clone, Debug)]
 #[allow(non_snake_case)]
 pub struct HPolytope {
     A: Array2<f64>,
     b: Array1<f64>,
+    Ae: Array2<f64>,
+    be: Array1<f64>,
 }
 
 #[derive(Error, Debug)]
 pub enum HPolytopeError {
     #[error("Dimensions of A {a_dim:?} and b {b_dim:?} do not match")]
     DimensionMismatch { a_dim: (usize, usize), b_dim: usize },
+    #[error("Dimensions of Ae {ae_dim:?} and be {be_dim:?} do not match dimension {dim}")]
+    EqualityDimensionMismatch {
+        ae_dim: (usize, usize),
+        be_dim: usize,
+        dim: usize,
+    },
 }
 
 #[allow(non_snake_case)]
@@ -32,8 +50,35 @@ impl HPolytope {
                 b_dim: b.dim(),
             })
         } else {
-            Ok(HPolytope { A, b })
+            let dim = A.ncols();
+            Ok(HPolytope {
+                A,
+                b,
+                Ae: Array2::zeros((0, dim)),
+                be: Array1::zeros(0),
+            })
+        }
+    }
+
+    /// Creates the polyhedron $\{x : Ax \leq b, A_e x = b_e\}$.
+    pub fn new_with_equalities(
+        A: Array2<f64>,
+        b: Array1<f64>,
+        Ae: Array2<f64>,
+        be: Array1<f64>,
+    ) -> Result<HPolytope, HPolytopeError> {
+        if Ae.nrows() != be.dim() || Ae.ncols() != A.ncols() {
+            return Err(HPolytopeError::EqualityDimensionMismatch {
+                ae_dim: Ae.dim(),
+                be_dim: be.dim(),
+                dim: A.ncols(),
+            });
         }
+
+        let mut hpoly = HPolytope::new(A, b)?;
+        hpoly.Ae = Ae;
+        hpoly.be = be;
+        Ok(hpoly)
     }
 
     pub fn from_random(dim: usize, n_constraints: usize) -> Result<HPolytope, HPolytopeError> {
@@ -56,12 +101,311 @@ impl HPolytope {
         let A = ndarray::concatenate(Axis(0), &[box_poly.A.view(), random_A.view()]).unwrap();
         let b = ndarray::concatenate(Axis(0), &[box_poly.b.view(), random_b.view()]).unwrap();
 
-        Ok(HPolytope { A, b })
+        HPolytope::new(A, b)
     }
 
     pub fn n_constraints(&self) -> usize {
         self.A.nrows()
     }
+
+    pub fn n_equalities(&self) -> usize {
+        self.Ae.nrows()
+    }
+
+    pub fn A(&self) -> &Array2<f64> {
+        &self.A
+    }
+
+    pub fn b(&self) -> &Array1<f64> {
+        &self.b
+    }
+
+    pub fn Ae(&self) -> &Array2<f64> {
+        &self.Ae
+    }
+
+    pub fn be(&self) -> &Array1<f64> {
+        &self.be
+    }
+
+    /// Computes the vertices, extreme rays and lines of the polyhedron with cddlib.
+    /// The equality constraints are passed to cddlib as its linearity set.
+    pub fn generators(&self) -> Result<PolyhedronGenerators, SetOperationError> {
+        compute_polyhedron_generators_with_equalities(&self.A, &self.b, &self.Ae, &self.be)
+    }
+
+    /// Constraints $A_e x = b_e$ on the variables `x`
+    fn equality_constraints(&self, x: &[Variable]) -> Vec<Constraint> {
+        self.Ae
+            .outer_iter()
+            .zip(self.be.iter())
+            .map(|(row, &be_i)| {
+                let expr: Expression = row.iter().zip(x).map(|(a, x_j)| *a * *x_j).sum();
+                expr.eq(be_i)
+            })
+            .collect()
+    }
+
+    /// Orthonormal basis of the null space of $A_e$, one vector per column
+    fn equality_null_space(&self) -> Array2<f64> {
+        if self.n_equalities() == 0 {
+            return Array2::eye(self.dim());
+        }
+        let (_, s, vt) = self.Ae.svd(false, true).unwrap();
+        let equality_rank = s.iter().filter(|&&value| value > 1e-10).count();
+        vt.unwrap().slice(s![equality_rank.., ..]).t().to_owned()
+    }
+
+    fn has_equalities(&self) -> bool {
+        self.n_equalities() > 0 && rank(&self.Ae).unwrap() > 0
+    }
+
+    /// Checks whether the polyhedron is bounded, i.e., whether its recession cone
+    /// $\{d : Ad \leq 0\}$ only contains the origin. Empty sets are bounded.
+    pub fn is_bounded(&self) -> Result<bool, SetOperationError> {
+        if self.empty()? {
+            return Ok(true);
+        }
+        Ok(self.generators()?.is_bounded())
+    }
+
+    /// Support along a direction in which the polyhedron is unbounded: the value is $+\infty$
+    /// and the support vector is a ray or line of the polyhedron pointing along the direction.
+    fn unbounded_support(
+        &self,
+        direction: &Array1<f64>,
+    ) -> Result<(Array1<f64>, f64), SetOperationError> {
+        let generators = self.generators()?;
+        let lines = generators
+            .lines
+            .outer_iter()
+            .map(|line| line.dot(direction).signum() * &line);
+        let ray = generators
+            .rays
+            .outer_iter()
+            .map(|ray| ray.to_owned())
+            .chain(lines)
+            .find(|ray| ray.dot(direction) > 1e-9)
+            .unwrap_or_else(|| direction.clone());
+
+        Ok((ray, f64::INFINITY))
+    }
+
+    /// Eliminates the variable `k` with the equality constraint `i` by substituting
+    /// $x_k = (b_{e,i} - \sum_{j \neq k} A_{e,ij} x_j) / A_{e,ik}$ into all other constraints.
+    fn substitute_equality(&mut self, i: usize, k: usize) {
+        let pivot_row = self.Ae.row(i).to_owned() / self.Ae[[i, k]];
+        let pivot_b = self.be[i] / self.Ae[[i, k]];
+
+        for (mut row, b_j) in self
+            .A
+            .rows_mut()
+            .into_iter()
+            .zip(self.b.iter_mut())
+            .chain(self.Ae.rows_mut().into_iter().zip(self.be.iter_mut()))
+        {
+            let factor = row[k];
+            row.scaled_add(-factor, &pivot_row);
+            *b_j -= factor * pivot_b;
+        }
+
+        let columns: Vec<usize> = (0..self.dim()).filter(|&j| j != k).collect();
+        let equalities: Vec<usize> = (0..self.n_equalities()).filter(|&j| j != i).collect();
+        self.A = self.A.select(Axis(1), &columns);
+        self.Ae = self
+            .Ae
+            .select(Axis(0), &equalities)
+            .select(Axis(1), &columns);
+        self.be = self.be.select(Axis(0), &equalities);
+    }
+
+    /// Eliminates the variable `k` by Fourier–Motzkin elimination.
+    /// Every pair of constraints with opposite signs in column `k` is combined into one
+    /// constraint without it, the constraints with a zero coefficient are kept.
+    /// The equality constraints must not depend on the variable.
+    fn eliminate_variable(&mut self, k: usize) {
+        let tol = 1e-12;
+        let positive: Vec<usize> = (0..self.n_constraints())
+            .filter(|&i| self.A[[i, k]] > tol)
+            .collect();
+        let negative: Vec<usize> = (0..self.n_constraints())
+            .filter(|&i| self.A[[i, k]] < -tol)
+            .collect();
+
+        let mut rows: Vec<(Array1<f64>, f64)> = (0..self.n_constraints())
+            .filter(|&i| self.A[[i, k]].abs() <= tol)
+            .map(|i| (self.A.row(i).to_owned(), self.b[i]))
+            .collect();
+
+        for &p in &positive {
+            for &n in &negative {
+                let (a_p, a_n) = (self.A[[p, k]], -self.A[[n, k]]);
+                let row = &self.A.row(p) * a_n + &self.A.row(n) * a_p;
+                rows.push((row, self.b[p] * a_n + self.b[n] * a_p));
+            }
+        }
+
+        let columns: Vec<usize> = (0..self.dim()).filter(|&j| j != k).collect();
+        let mut A = Array2::zeros((rows.len(), columns.len()));
+        let mut b = Array1::zeros(rows.len());
+        for (i, (row, b_i)) in rows.iter().enumerate() {
+            A.row_mut(i).assign(&row.select(Axis(0), &columns));
+            b[i] = *b_i;
+        }
+
+        self.A = A;
+        self.b = b;
+        self.Ae = self.Ae.select(Axis(1), &columns);
+    }
+
+    /// Feasible point closest to `point` in the 1-norm, which starts the active set method of
+    /// [`GeoSet::closest_point`]: \
+    /// $\min \sum_i t_i$ \
+    /// $\text{subject to } -t \leq x - p \leq t, Ax \leq b, A_e x = b_e$
+    fn closest_point_l1(&self, point: &Array1<f64>) -> Result<Array1<f64>, SetOperationError> {
+        let mut vars = variables!();
+        let x: Vec<_> = (0..self.dim()).map(|_| vars.add(variable())).collect();
+        let t: Vec<_> = (0..self.dim())
+            .map(|_| vars.add(variable().min(0.0)))
+            .collect();
+
+        let objective: Expression = t.iter().copied().sum();
+        let mut problem = vars.minimise(objective).using(default_solver);
+        for ((&x_i, &t_i), &p_i) in x.iter().zip(&t).zip(point) {
+            problem = problem.with((x_i - t_i).leq(p_i));
+            problem = problem.with((x_i + t_i).geq(p_i));
+        }
+        for (row, &b_i) in self.A.outer_iter().zip(self.b.iter()) {
+            let expr: Expression = row.iter().zip(&x).map(|(a, x_j)| *a * *x_j).sum();
+            problem = problem.with(expr.leq(b_i));
+        }
+        for constraint in self.equality_constraints(&x) {
+            problem = problem.with(constraint);
+        }
+
+        let solution = match problem.solve() {
+            Ok(solution) => solution,
+            Err(ResolutionError::Infeasible) => return Err(SetOperationError::EmptySet),
+            Err(e) => {
+                return Err(SetOperationError::InfeasibleOptimization {
+                    source: Box::new(e),
+                });
+            }
+        };
+        Ok(x.iter().map(|&x_i| solution.value(x_i)).collect())
+    }
+
+    /// Scales the rows to unit norm, merges parallel constraints into the tightest one and sorts
+    /// the constraints lexicographically, so equal polytopes with the same facets have the same
+    /// representation. Zero rows are dropped.
+    /// Returns [`SetOperationError::EmptySet`] for a zero row with $b_i < 0$.
+    pub fn normalize_(&mut self) -> Result<(), SetOperationError> {
+        let mut rows: Vec<(Array1<f64>, f64)> = Vec::new();
+        for (row, &b_i) in self.A.outer_iter().zip(self.b.iter()) {
+            let norm = row.norm_l2();
+            if norm < 1e-12 {
+                if b_i < 0.0 {
+                    return Err(SetOperationError::EmptySet);
+                }
+                // 0 <= b_i is always satisfied
+                continue;
+            }
+            let (a, b_i) = (&row / norm, b_i / norm);
+
+            match rows
+                .iter_mut()
+                .find(|(other, _)| vector_all_close(other, &a, 1e-9))
+            {
+                Some((_, other_b)) => *other_b = other_b.min(b_i),
+                None => rows.push((a, b_i)),
+            }
+        }
+
+        rows.sort_by(|(a, b_a), (c, b_c)| {
+            a.iter()
+                .zip(c.iter())
+                .map(|(x, y)| x.total_cmp(y))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or_else(|| b_a.total_cmp(b_c))
+        });
+        self.set_rows(&rows);
+        Ok(())
+    }
+
+    pub fn normalize(&self) -> Result<HPolytope, SetOperationError> {
+        let mut copy = self.clone();
+        copy.normalize_()?;
+        Ok(copy)
+    }
+
+    /// Normalizes the polytope and removes the constraints that are implied by the others,
+    /// which is checked with one LP per constraint: \
+    /// $\max a_i^\top x$ \
+    /// $\text{subject to } a_j^\top x \leq b_j, j \neq i, a_i^\top x \leq b_i + 1$ \
+    /// Returns [`SetOperationError::EmptySet`] if the polytope is empty.
+    pub fn remove_redundant_(&mut self) -> Result<(), SetOperationError> {
+        self.normalize_()?;
+        let rows: Vec<(Array1<f64>, f64)> = self
+            .A
+            .outer_iter()
+            .zip(self.b.iter())
+            .map(|(row, &b_i)| (row.to_owned(), b_i))
+            .collect();
+
+        let mut keep = vec![true; rows.len()];
+        for i in 0..rows.len() {
+            let mut vars = variables!();
+            let x: Vec<_> = (0..self.dim()).map(|_| vars.add(variable())).collect();
+
+            let expr = |a: &Array1<f64>| -> Expression {
+                a.iter().zip(&x).map(|(coef, xi)| *coef * *xi).sum()
+            };
+            let objective = expr(&rows[i].0);
+            let mut problem = vars.maximise(objective.clone()).using(default_solver);
+
+            problem = problem.with(objective.clone().leq(rows[i].1 + 1.0));
+            for constraint in self.equality_constraints(&x) {
+                problem = problem.with(constraint);
+            }
+            for (j, (a, b_j)) in rows.iter().enumerate() {
+                if j != i && keep[j] {
+                    problem = problem.with(expr(a).leq(*b_j));
+                }
+            }
+
+            match problem.solve() {
+                Ok(solution) => keep[i] = solution.eval(&objective) > rows[i].1 + 1e-9,
+                Err(_) => return Err(SetOperationError::EmptySet),
+            }
+        }
+
+        let kept: Vec<(Array1<f64>, f64)> = rows
+            .into_iter()
+            .zip(keep.iter())
+            .filter(|(_, keep)| **keep)
+            .map(|(row, _)| row)
+            .collect();
+        self.set_rows(&kept);
+        Ok(())
+    }
+
+    pub fn remove_redundant(&self) -> Result<HPolytope, SetOperationError> {
+        let mut copy = self.clone();
+        copy.remove_redundant_()?;
+        Ok(copy)
+    }
+
+    fn set_rows(&mut self, rows: &[(Array1<f64>, f64)]) {
+        let mut A = Array2::zeros((rows.len(), self.dim()));
+        let mut b = Array1::zeros(rows.len());
+        for (i, (row, b_i)) in rows.iter().enumerate() {
+            A.row_mut(i).assign(row);
+            b[i] = *b_i;
+        }
+
+        self.A = A;
+        self.b = b;
+    }
 }
 
 #[allow(non_snake_case)]
@@ -82,7 +426,7 @@ impl GeoSet for HPolytope {
 
     /// Evaluates the feasibility of the optimization problem
     /// $\min 0$ \
-    /// $\text{subject to } A^\top x \leq b$ \
+    /// $\text{subject to } A^\top x \leq b, A_e x = b_e$ \
     fn empty(&self) -> Result<bool, SetOperationError> {
         // Define variables x_0, ..., x_{n-1} (unbounded)
         let mut vars = variables!();
@@ -97,6 +441,9 @@ impl GeoSet for HPolytope {
             let expr: Expression = row.iter().zip(&x).map(|(coef, xi)| *coef * *xi).sum();
             problem = problem.with(expr.leq(self.b[i]));
         }
+        for constraint in self.equality_constraints(&x) {
+            problem = problem.with(constraint);
+        }
 
         // Try solving
         match problem.solve() {
@@ -110,12 +457,19 @@ impl GeoSet for HPolytope {
         if empty {
             return Err(SetOperationError::EmptySet);
         }
-        compute_polytope_vertices(&self.A, &self.b)
+        let generators = self.generators()?;
+        if !generators.is_bounded() {
+            return Err(SetOperationError::UnsupportedOperation {
+                message: "the polyhedron is unbounded and has no vertex representation".to_string(),
+            });
+        }
+        Ok(generators.vertices)
     }
 
-    /// Solves the optimization problem: \
-    /// $\max c^\top x $ \
-    /// $\text{subject to } A^\top x \leq b$ \
+    /// Computes the Chebyshev center within the affine hull $A_e x = b_e$ by solving \
+    /// $\max r$ \
+    /// $\text{subject to } a_i^\top x + \|N^\top a_i\|_2 r \leq b_i, A_e x = b_e$ \
+    /// where the columns of $N$ span the null space of $A_e$.
     fn center(&self) -> Result<Array1<f64>, SetOperationError> {
         let mut vars = variables!();
         let r = vars.add(variable().min(0.0));
@@ -124,12 +478,16 @@ impl GeoSet for HPolytope {
         // maximize radius
         let mut problem = vars.maximise(r).using(default_solver);
 
-        // constraints: a_i^T x + ||a_i|| * r <= b_i
+        // constraints: a_i^T x + ||N^T a_i|| * r <= b_i
+        let null_space = self.equality_null_space();
         for (i, row) in self.A.outer_iter().enumerate() {
-            let norm_ai = row.dot(&row).sqrt();
+            let norm_ai = null_space.t().dot(&row).norm_l2();
             let lhs: Expression = row.iter().zip(&x).map(|(&aij, &xj)| aij * xj).sum();
             problem = problem.with((lhs + norm_ai * r).leq(self.b[i]));
         }
+        for constraint in self.equality_constraints(&x) {
+            problem = problem.with(constraint);
+        }
 
         let solution = problem
             .solve()
@@ -148,7 +506,8 @@ impl GeoSet for HPolytope {
 
     /// Solves the optimization problem: \
     /// $\max d^\top x $ \
-    /// $\text{subject to } A^\top x \leq b$ \
+    /// $\text{subject to } A^\top x \leq b, A_e x = b_e$ \
+    /// If the problem is unbounded, the support value is $+\infty$.
     fn support_function(
         &self,
         direction: Array1<f64>,
@@ -169,12 +528,19 @@ impl GeoSet for HPolytope {
             let expr: Expression = row.iter().zip(&x).map(|(coef, xi)| *coef * *xi).sum();
             problem = problem.with(expr.leq(self.b[i]));
         }
+        for constraint in self.equality_constraints(&x) {
+            problem = problem.with(constraint);
+        }
 
-        let solution = problem
-            .solve()
-            .map_err(|e| SetOperationError::InfeasibleOptimization {
-                source: Box::new(e),
-            })?;
+        let solution = match problem.solve() {
+            Ok(solution) => solution,
+            Err(ResolutionError::Unbounded) => return self.unbounded_support(&direction),
+            Err(e) => {
+                return Err(SetOperationError::InfeasibleOptimization {
+                    source: Box::new(e),
+                });
+            }
+        };
 
         let support_vector =
             Array1::from_shape_vec(self.dim(), x.iter().map(|&xi| solution.value(xi)).collect())
@@ -188,6 +554,12 @@ impl GeoSet for HPolytope {
     }
 
     fn volume(&self) -> Result<f64, SetOperationError> {
+        if self.has_equalities() {
+            if self.empty()? {
+                return Err(SetOperationError::EmptySet);
+            }
+            return Ok(0.0);
+        }
         let vpoly = VPolytope::new(self.to_vertices()?).map_err(|_| SetOperationError::EmptySet)?;
         vpoly.volume()
     }
@@ -196,15 +568,31 @@ impl GeoSet for HPolytope {
         // Implementation based on the support functions of both
         let dim = self.dim();
 
-        // Collect candidate directions: normals from both polytopes
-        let directions = ndarray::concatenate![Axis(0), self.A.view(), other.A.view()];
-
-        // Prepare storage for new A and b
-        let n_dirs = directions.nrows();
-        let mut new_A = Array2::<f64>::zeros((n_dirs, dim));
-        let mut new_b = Array1::<f64>::zeros(n_dirs);
+        // Both polytopes are flat along the directions orthogonal to both null spaces of A_e,
+        // and so is the sum
+        let null_spaces = concatenate![
+            Axis(0),
+            self.equality_null_space().t(),
+            other.equality_null_space().t()
+        ];
+        let flat_directions =
+            null_space_basis(&null_spaces).map_err(|e| SetOperationError::DataConversionError {
+                source: e.to_string().into(),
+            })?;
 
-        for (i, dir) in directions.outer_iter().enumerate() {
+        // Collect candidate directions: normals from both polytopes
+        let directions = concatenate![
+            Axis(0),
+            self.A.view(),
+            other.A.view(),
+            self.Ae.view(),
+            (-&self.Ae).view(),
+            other.Ae.view(),
+            (-&other.Ae).view()
+        ];
+
+        let mut rows: Vec<(Array1<f64>, f64)> = Vec::new();
+        for dir in directions.outer_iter() {
             // Normalize direction to avoid scaling issues
             let norm = dir.norm_l2();
             if norm < 1e-9 {
@@ -212,42 +600,224 @@ impl GeoSet for HPolytope {
             }
             let u = &dir / norm;
 
+            // Directions along which both are flat become equality constraints
+            let residual = &u - &flat_directions.t().dot(&flat_directions.dot(&u));
+            if residual.norm_l2() < 1e-9 {
+                continue;
+            }
+
             // Compute support values
             let (_, h1) = self.support_function(u.clone())?;
             let (_, h2) = other.support_function(u.clone())?;
-
-            // Fill row in A and entry in b
-            new_A.row_mut(i).assign(&u);
-            new_b[i] = h1 + h2;
+            rows.push((u, h1 + h2));
         }
 
-        // Replace self with new H-representation
-        self.A = new_A;
-        self.b = new_b;
+        let mut be = Array1::zeros(flat_directions.nrows());
+        for (be_i, u) in be.iter_mut().zip(flat_directions.outer_iter()) {
+            let (_, h1) = self.support_function(u.to_owned())?;
+            let (_, h2) = other.support_function(u.to_owned())?;
+            *be_i = h1 + h2;
+        }
 
-        Ok(())
+        // Replace self with new H-representation, merging the shared normals of both
+        self.set_rows(&rows);
+        self.Ae = flat_directions;
+        self.be = be;
+        self.normalize_()
     }
 
+    /// Computes the image $\{M x : A x \leq b\}$ for any $m \times n$ matrix $M = U \Sigma V^\top$.
+    ///
+    /// Injective maps (including invertible ones) result in $A M^+ y \leq b, A_e M^+ y = b_e$,
+    /// lifted with the equality constraints $u_j^\top y = 0$ for the left null space of $M$.
+    /// The image of an empty polytope is empty for every rank of $M$.
+    /// Otherwise, the polytope is rotated by $V$ and projected onto the $r = \mathrm{rank}(M)$
+    /// coordinates of the row space before the injective map $U_r \Sigma_r$ is applied.
     fn matmul_(&mut self, mat: &Array2<f64>) -> Result<(), SetOperationError> {
         let (m, n) = mat.dim();
-        self._check_operand_dim(m)?;
-        let mat_rank = rank(mat).unwrap();
+        self._check_operand_dim(n)?;
+
+        if self.empty()? {
+            *self = HPolytope::from_unit_box(m);
+            self.b.fill(-1.0);
+            return Ok(());
+        }
+
+        let (u, s, vt) =
+            mat.svd(true, true)
+                .map_err(|e| SetOperationError::DataConversionError {
+                    source: Box::new(e),
+                })?;
+        let (u, vt) = (u.unwrap(), vt.unwrap());
+        let r = s.iter().filter(|&&value| value > 1e-10).count();
+
+        if r < n {
+            if r == 0 {
+                // Everything is mapped to the origin
+                *self = HPolytope::from_unit_box(m);
+                self.b.fill(0.0);
+                return Ok(());
+            }
+
+            self.A = self.A.dot(&vt.t());
+            self.Ae = self.Ae.dot(&vt.t());
+            self.project_(&(0..r).collect::<Vec<_>>())?;
 
-        if m == n && mat_rank == n {
-            self.A = self.A.dot(mat);
-        } else if m > n {
-            return Err(SetOperationError::NotImplemented);
+            let injective = u
+                .slice(s![.., ..r])
+                .dot(&Array2::from_diag(&s.slice(s![..r])));
+            return self.matmul_(&injective);
         }
+
+        // Pseudo-inverse V \Sigma^{-1} U_r^\top
+        let u_r = u.slice(s![.., ..r]);
+        let pinv = vt
+            .t()
+            .dot(&Array2::from_diag(&s.mapv(|x| 1. / x)))
+            .dot(&u_r.t());
+
+        self.A = self.A.dot(&pinv);
+        self.Ae = concatenate![Axis(0), self.Ae.dot(&pinv), u.slice(s![.., r..]).t()];
+        self.be = concatenate![Axis(0), self.be, Array1::zeros(m - r)];
         Ok(())
     }
 
     fn translate_(&mut self, vector: &Array1<f64>) -> Result<(), SetOperationError> {
         self._check_operand_dim(vector.dim())?;
         self.b = &self.b + &self.A.dot(vector);
+        self.be = &self.be + &self.Ae.dot(vector);
+        Ok(())
+    }
+
+    /// Stacks the constraints of both polytopes: $A = [A_1; A_2]$, $b = [b_1; b_2]$ and
+    /// likewise for the equality constraints
+    fn intersection_(&mut self, other: &Self) -> Result<(), SetOperationError> {
+        self._check_operand_dim(other.dim())?;
+
+        let intersection = HPolytope {
+            A: ndarray::concatenate![Axis(0), self.A.view(), other.A.view()],
+            b: ndarray::concatenate![Axis(0), self.b.view(), other.b.view()],
+            Ae: ndarray::concatenate![Axis(0), self.Ae.view(), other.Ae.view()],
+            be: ndarray::concatenate![Axis(0), self.be.view(), other.be.view()],
+        };
+
+        if intersection.empty()? {
+            return Err(SetOperationError::EmptySet);
+        }
+
+        *self = intersection;
+        Ok(())
+    }
+
+    /// Block-diagonal constraints $A = \mathrm{diag}(A_1, A_2)$, $b = [b_1; b_2]$
+    fn cartesian_product_(&mut self, other: &Self) -> Result<(), SetOperationError> {
+        self.A = block_diag(&self.A, &other.A);
+        self.b = concatenate![Axis(0), self.b, other.b];
+        self.Ae = block_diag(&self.Ae, &other.Ae);
+        self.be = concatenate![Axis(0), self.be, other.be];
+        Ok(())
+    }
+
+    /// Substitutes the equality constraints for the eliminated variables first, pivoting on the
+    /// largest coefficient. The remaining variables are eliminated by Fourier–Motzkin
+    /// elimination, choosing the variable that creates the fewest constraints first and pruning
+    /// redundant constraints after every step. Equality constraints on the kept variables stay.
+    fn project_(&mut self, dims: &[usize]) -> Result<(), SetOperationError> {
+        self._check_projection_dims(dims)?;
+        if self.empty()? {
+            return Err(SetOperationError::EmptySet);
+        }
+
+        // Original indices of the remaining columns
+        let mut remaining: Vec<usize> = (0..self.dim()).collect();
+
+        loop {
+            let pivot = (0..self.n_equalities())
+                .cartesian_product(0..remaining.len())
+                .filter(|&(_, k)| !dims.contains(&remaining[k]))
+                .max_by(|&(i, k), &(j, l)| self.Ae[[i, k]].abs().total_cmp(&self.Ae[[j, l]].abs()))
+                .filter(|&(i, k)| self.Ae[[i, k]].abs() > 1e-9);
+            let Some((i, k)) = pivot else {
+                break;
+            };
+            self.substitute_equality(i, k);
+            remaining.remove(k);
+        }
+
+        // Drop the equalities that became trivial
+        let nontrivial: Vec<usize> = (0..self.n_equalities())
+            .filter(|&i| self.Ae.row(i).norm_l2() > 1e-9)
+            .collect();
+        self.Ae = self.Ae.select(Axis(0), &nontrivial);
+        self.be = self.be.select(Axis(0), &nontrivial);
+        self.remove_redundant_()?;
+
+        while remaining.len() > dims.len() {
+            let candidates = (0..remaining.len()).filter(|&k| !dims.contains(&remaining[k]));
+            let k = candidates
+                .min_by_key(|&k| {
+                    let column = self.A.column(k);
+                    let n_pos = column.iter().filter(|&&a| a > 1e-12).count();
+                    let n_neg = column.iter().filter(|&&a| a < -1e-12).count();
+                    n_pos * n_neg
+                })
+                .unwrap();
+
+            self.eliminate_variable(k);
+            remaining.remove(k);
+            self.remove_redundant_()?;
+        }
+
+        // Reorder the columns as requested
+        let order: Vec<usize> = dims
+            .iter()
+            .map(|d| remaining.iter().position(|r| r == d).unwrap())
+            .collect();
+        self.A = self.A.select(Axis(1), &order);
+        self.Ae = self.Ae.select(Axis(1), &order);
+        Ok(())
+    }
+
+    /// Enumerates the vertices of both polytopes and computes the facets of their hull
+    fn convex_hull_(&mut self, other: &Self) -> Result<(), SetOperationError> {
+        self._check_operand_dim(other.dim())?;
+        let vertices = concatenate![Axis(0), self.to_vertices()?, other.to_vertices()?];
+        *self = VPolytope::new(vertices)
+            .map_err(|e| SetOperationError::DataConversionError {
+                source: Box::new(e),
+            })?
+            .to_hpolytope()?;
+        Ok(())
+    }
+
+    /// Shrinks each constraint by the support function of `other`: $b_i - \rho_{other}(A_i)$.
+    /// An equality constraint can only be kept if `other` is flat along its normal.
+    fn pontryagin_difference_<S: GeoSet>(&mut self, other: &S) -> Result<(), SetOperationError> {
+        self._check_operand_dim(other.dim())?;
+
+        for (row, b_i) in self.A.outer_iter().zip(self.b.iter_mut()) {
+            let (_, support_value) = other.support_function(row.to_owned())?;
+            *b_i -= support_value;
+        }
+        for (row, be_i) in self.Ae.outer_iter().zip(self.be.iter_mut()) {
+            let (_, upper) = other.support_function(row.to_owned())?;
+            let (_, lower) = other.support_function(-&row)?;
+            if upper + lower > 1e-9 {
+                return Err(SetOperationError::EmptySet);
+            }
+            *be_i -= upper;
+        }
+
+        if self.empty()? {
+            return Err(SetOperationError::EmptySet);
+        }
         Ok(())
     }
 
     fn degenerate(&self) -> bool {
+        if self.has_equalities() {
+            return true;
+        }
         let c = match self.center() {
             Ok(center) => center,
             Err(SetOperationError::InfeasibleOptimization { .. }) => {
@@ -268,7 +838,111 @@ impl GeoSet for HPolytope {
 
     fn contains_point(&self, point: &Array1<f64>) -> Result<bool, SetOperationError> {
         self._check_operand_dim(point.dim())?;
-        Ok(vector_leq(&self.A.dot(point), &self.b))
+        let residual = &self.Ae.dot(point) - &self.be;
+        Ok(vector_leq(&self.A.dot(point), &self.b) && residual.iter().all(|r| r.abs() <= 1e-9))
+    }
+
+    /// Solves the quadratic program \
+    /// $\min \frac{1}{2} \|x - p\|_2^2$ \
+    /// $\text{subject to } Ax \leq b, A_e x = b_e$ \
+    /// with the primal active set method, starting from the feasible point closest in the 1-norm.
+    /// The step $-(x - p + M^\top \lambda)$ onto the null space of the working constraints $M$
+    /// is taken until a constraint blocks it, which then joins the working set.
+    /// Once the step vanishes, the constraint with the most negative multiplier $\lambda_i$ leaves
+    /// the working set, and without negative multipliers $x$ satisfies the KKT conditions.
+    fn closest_point(&self, point: &Array1<f64>) -> Result<Array1<f64>, SetOperationError> {
+        self._check_operand_dim(point.dim())?;
+        let tol = 1e-9;
+
+        let mut x = self.closest_point_l1(point)?;
+        let equalities =
+            row_space_basis(&self.Ae).map_err(|e| SetOperationError::DataConversionError {
+                source: e.to_string().into(),
+            })?;
+        let mut working: Vec<usize> = Vec::new();
+
+        for _ in 0..MAX_ACTIVE_SET_ITERATIONS {
+            let mut M = Array2::zeros((equalities.nrows() + working.len(), self.dim()));
+            M.slice_mut(s![..equalities.nrows(), ..])
+                .assign(&equalities);
+            for (mut row, &i) in M
+                .slice_mut(s![equalities.nrows().., ..])
+                .outer_iter_mut()
+                .zip(&working)
+            {
+                row.assign(&self.A.row(i));
+            }
+
+            // Multipliers of the equality constrained problem, M M^T lambda = -M (x - p)
+            let gradient = &x - point;
+            let multipliers = if M.nrows() == 0 {
+                Array1::zeros(0)
+            } else {
+                M.dot(&M.t()).solve(&(-M.dot(&gradient))).map_err(|e| {
+                    SetOperationError::DataConversionError {
+                        source: Box::new(e),
+                    }
+                })?
+            };
+            let step = -(&gradient + &M.t().dot(&multipliers));
+
+            if step.norm_l2() <= tol {
+                let inequality_multipliers = multipliers.slice(s![equalities.nrows()..]).to_owned();
+                match argmin(&inequality_multipliers) {
+                    Some(k) if inequality_multipliers[k] < -tol => {
+                        working.remove(k);
+                    }
+                    _ => return Ok(x),
+                }
+                continue;
+            }
+
+            let mut step_length = 1.0;
+            let mut blocking = None;
+            for (i, (row, &b_i)) in self.A.outer_iter().zip(self.b.iter()).enumerate() {
+                let rate = row.dot(&step);
+                if working.contains(&i) || rate <= 1e-12 {
+                    continue;
+                }
+                let length = ((b_i - row.dot(&x)) / rate).max(0.0);
+                if length < step_length {
+                    step_length = length;
+                    blocking = Some(i);
+                }
+            }
+
+            x = x + step_length * &step;
+            if let Some(i) = blocking {
+                working.push(i);
+            }
+        }
+
+        Err(SetOperationError::UnsupportedOperation {
+            message: "active set method did not converge".to_string(),
+        })
+    }
+
+    /// Inside the polyhedron the distance to the boundary is the smallest distance to a
+    /// constraint hyperplane. Polyhedra with equality constraints have no interior.
+    fn signed_distance(&self, point: &Array1<f64>) -> Result<f64, SetOperationError> {
+        let distance = self.distance_to_point(point)?;
+        if distance > 1e-9 {
+            return Ok(distance);
+        }
+        if self.has_equalities() {
+            return Ok(0.0);
+        }
+
+        let depth = self
+            .A
+            .outer_iter()
+            .zip(self.b.iter())
+            .filter_map(|(row, &b_i)| {
+                let norm = row.norm_l2();
+                (norm > 1e-12).then(|| (b_i - row.dot(point)) / norm)
+            })
+            .fold(f64::INFINITY, f64::min);
+        Ok(if depth > 0.0 { -depth } else { 0.0 })
     }
 }
 
@@ -276,6 +950,35 @@ impl GeoSet for HPolytope {
 mod tests {
     use super::*;
 
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_normalize() {
+        let A = array![[0.0, 2.0], [1.0, 0.0], [0.0, 0.0], [0.0, 1.0], [-1.0, 0.0]];
+        let b = array![4.0, 1.0, 0.5, 3.0, 1.0];
+        let hpoly = HPolytope::new(A, b).unwrap().normalize().unwrap();
+
+        assert_eq!(hpoly.A(), &array![[-1.0, 0.0], [0.0, 1.0], [1.0, 0.0]]);
+        assert_eq!(hpoly.b(), &array![1.0, 2.0, 1.0]);
+
+        let infeasible = HPolytope::new(array![[0.0, 0.0]], array![-1.0]).unwrap();
+        assert!(matches!(
+            infeasible.normalize(),
+            Err(SetOperationError::EmptySet)
+        ));
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_remove_redundant() {
+        let box_poly = HPolytope::from_unit_box(2);
+        let A = concatenate![Axis(0), *box_poly.A(), array![[1.0, 1.0], [2.0, 0.0]]];
+        let b = concatenate![Axis(0), *box_poly.b(), array![5.0, 4.0]];
+
+        let hpoly = HPolytope::new(A, b).unwrap().remove_redundant().unwrap();
+        assert_eq!(hpoly.n_constraints(), 4);
+        assert_eq!(hpoly.A(), box_poly.normalize().unwrap().A());
+    }
+
     #[test]
     fn test_polytope_new() {
         let _ = HPolytope::new(Array::ones((2, 2)), Array::ones(2)).unwrap();
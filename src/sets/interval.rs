@@ -1,7 +1,9 @@
 #![allow(unused)]
 use crate::linalg_utils::vector_leq;
+use crate::{HPolytope, VPolytope, Zonotope};
 
 use super::*;
+use ndarray::Zip;
 use ndarray_rand::RandomExt;
 use ndarray_rand::rand_distr::{Exp1, Uniform};
 use thiserror::Error;
@@ -44,6 +46,87 @@ impl Interval {
 
         Interval::new(lb, ub)
     }
+
+    pub fn lb(&self) -> &Array1<f64> {
+        &self.lb
+    }
+
+    pub fn ub(&self) -> &Array1<f64> {
+        &self.ub
+    }
+
+    /// Indices of the dimensions with non-zero width
+    fn nondegenerate_dims(&self) -> Vec<usize> {
+        (0..self.dim())
+            .filter(|&i| self.ub[i] > self.lb[i])
+            .collect()
+    }
+}
+
+/// Axis-aligned generators with the half-widths of the interval.
+/// Dimensions with `lb == ub` do not produce a generator.
+#[allow(non_snake_case)]
+impl From<Interval> for Zonotope {
+    fn from(interval: Interval) -> Self {
+        let dims = interval.nondegenerate_dims();
+        let radius = (&interval.ub - &interval.lb) / 2.;
+
+        let mut G = Array2::zeros((dims.len(), interval.dim()));
+        for (row, &i) in dims.iter().enumerate() {
+            G[[row, i]] = radius[i];
+        }
+
+        Zonotope::new(G, interval.center().unwrap()).unwrap()
+    }
+}
+
+/// Constraints $x_i \leq ub_i$ and $-x_i \leq -lb_i$.
+/// Dimensions with `lb == ub` result in an equality $x_i = lb_i$ instead.
+#[allow(non_snake_case)]
+impl From<Interval> for HPolytope {
+    fn from(interval: Interval) -> Self {
+        let dim = interval.dim();
+        let dims = interval.nondegenerate_dims();
+        let flat_dims: Vec<usize> = (0..dim).filter(|i| !dims.contains(i)).collect();
+
+        let mut A = Array2::zeros((2 * dims.len(), dim));
+        let mut b = Array1::zeros(2 * dims.len());
+        for (row, &i) in dims.iter().enumerate() {
+            A[[row, i]] = 1.0;
+            b[row] = interval.ub[i];
+            A[[dims.len() + row, i]] = -1.0;
+            b[dims.len() + row] = -interval.lb[i];
+        }
+
+        let mut Ae = Array2::zeros((flat_dims.len(), dim));
+        let mut be = Array1::zeros(flat_dims.len());
+        for (row, &i) in flat_dims.iter().enumerate() {
+            Ae[[row, i]] = 1.0;
+            be[row] = interval.lb[i];
+        }
+
+        HPolytope::new_with_equalities(A, b, Ae, be).unwrap()
+    }
+}
+
+/// Corners of the interval.
+/// Dimensions with `lb == ub` do not multiply the number of vertices.
+impl From<Interval> for VPolytope {
+    fn from(interval: Interval) -> Self {
+        let dims = interval.nondegenerate_dims();
+
+        let mut vertices = Array2::zeros((1 << dims.len(), interval.dim()));
+        for (i, mut vertex) in vertices.rows_mut().into_iter().enumerate() {
+            vertex.assign(&interval.lb);
+            for (bit, &j) in dims.iter().enumerate() {
+                if (i & (1 << bit)) != 0 {
+                    vertex[j] = interval.ub[j];
+                }
+            }
+        }
+
+        VPolytope::new(vertices).unwrap()
+    }
 }
 
 #[allow(non_snake_case)]
@@ -120,10 +203,7 @@ impl GeoSet for Interval {
     }
 
     fn matmul_(&mut self, mat: &Array2<f64>) -> Result<(), SetOperationError> {
-        self._check_operand_dim(mat.dim().0);
-
-        let mat_lb = mat.dot(&self.lb);
-        let mat_ub = mat.dot(&self.ub);
+        self._check_operand_dim(mat.dim().1)?;
 
         // Create arrays for positive and negative parts of the matrix
         let mat_pos = mat.mapv(|x| x.max(0.0));
@@ -131,19 +211,94 @@ impl GeoSet for Interval {
 
         // For positive matrix elements: pos * [lb, ub] = [pos*lb, pos*ub]
         // For negative matrix elements: neg * [lb, ub] = [neg*ub, neg*lb]
-        self.lb = mat_pos.dot(&self.lb) + mat_neg.dot(&self.ub);
-        self.ub = mat_pos.dot(&self.ub) + mat_neg.dot(&self.lb);
+        let lb = mat_pos.dot(&self.lb) + mat_neg.dot(&self.ub);
+        let ub = mat_pos.dot(&self.ub) + mat_neg.dot(&self.lb);
+        self.lb = lb;
+        self.ub = ub;
 
         Ok(())
     }
 
     fn translate_(&mut self, vector: &Array1<f64>) -> Result<(), SetOperationError> {
-        self._check_operand_dim(vector.dim());
+        self._check_operand_dim(vector.dim())?;
         self.lb += vector;
         self.ub += vector;
         Ok(())
     }
 
+    fn intersection_(&mut self, other: &Self) -> Result<(), SetOperationError> {
+        self._check_operand_dim(other.dim())?;
+
+        let lb = self
+            .lb
+            .iter()
+            .zip(other.lb.iter())
+            .map(|(a, b)| a.max(*b))
+            .collect::<Array1<f64>>();
+        let ub = self
+            .ub
+            .iter()
+            .zip(other.ub.iter())
+            .map(|(a, b)| a.min(*b))
+            .collect::<Array1<f64>>();
+
+        // Crossing bounds mean that the intervals do not overlap in some dimension
+        if lb.iter().zip(ub.iter()).any(|(lb, ub)| lb > ub) {
+            return Err(SetOperationError::EmptySet);
+        }
+
+        self.lb = lb;
+        self.ub = ub;
+        Ok(())
+    }
+
+    fn cartesian_product_(&mut self, other: &Self) -> Result<(), SetOperationError> {
+        self.lb = concatenate![Axis(0), self.lb, other.lb];
+        self.ub = concatenate![Axis(0), self.ub, other.ub];
+        Ok(())
+    }
+
+    fn project_(&mut self, dims: &[usize]) -> Result<(), SetOperationError> {
+        self._check_projection_dims(dims)?;
+        self.lb = self.lb.select(Axis(0), dims);
+        self.ub = self.ub.select(Axis(0), dims);
+        Ok(())
+    }
+
+    /// Elementwise minimum and maximum of the bounds
+    fn convex_hull_(&mut self, other: &Self) -> Result<(), SetOperationError> {
+        self._check_operand_dim(other.dim())?;
+        self.lb.zip_mut_with(&other.lb, |a, &b| *a = a.min(b));
+        self.ub.zip_mut_with(&other.ub, |a, &b| *a = a.max(b));
+        Ok(())
+    }
+
+    /// Shrinks the bounds by the support function of `other` along $\pm e_i$
+    fn pontryagin_difference_<S: GeoSet>(&mut self, other: &S) -> Result<(), SetOperationError> {
+        self._check_operand_dim(other.dim())?;
+
+        for i in 0..self.dim() {
+            let mut direction = Array1::zeros(self.dim());
+            direction[i] = 1.0;
+
+            let (_, upper) = other.support_function(direction.clone())?;
+            let (_, lower) = other.support_function(-direction)?;
+            self.ub[i] -= upper;
+            self.lb[i] += lower;
+        }
+
+        if self.lb.iter().zip(self.ub.iter()).any(|(lb, ub)| lb > ub) {
+            return Err(SetOperationError::EmptySet);
+        }
+        Ok(())
+    }
+
+    /// Center and the axes of the dimensions with non-zero width
+    fn affine_hull(&self) -> Result<AffineHull, SetOperationError> {
+        let directions = Array2::<f64>::eye(self.dim()).select(Axis(0), &self.nondegenerate_dims());
+        AffineHull::from_directions(self.center()?, &directions)
+    }
+
     fn degenerate(&self) -> bool {
         self.lb
             .iter()
@@ -151,8 +306,42 @@ impl GeoSet for Interval {
             .any(|(lb, ub)| (ub - lb).abs() < 1e-9)
     }
 
+    fn interval_hull(&self) -> Result<Interval, SetOperationError> {
+        Ok(self.clone())
+    }
+
     fn contains_point(&self, point: &Array1<f64>) -> Result<bool, SetOperationError> {
         self._check_operand_dim(point.dim());
         Ok(vector_leq(&self.lb, point) && vector_leq(point, &self.ub))
     }
+
+    /// Clamps the point to the bounds
+    fn closest_point(&self, point: &Array1<f64>) -> Result<Array1<f64>, SetOperationError> {
+        self._check_operand_dim(point.dim())?;
+        Ok(Zip::from(point)
+            .and(&self.lb)
+            .and(&self.ub)
+            .map_collect(|&p, &lb, &ub| p.clamp(lb, ub)))
+    }
+
+    fn distance_to_point(&self, point: &Array1<f64>) -> Result<f64, SetOperationError> {
+        let diff = point - &self.closest_point(point)?;
+        Ok(diff.dot(&diff).sqrt())
+    }
+
+    /// Inside the interval the distance to the boundary is the smallest distance to a bound
+    fn signed_distance(&self, point: &Array1<f64>) -> Result<f64, SetOperationError> {
+        let distance = self.distance_to_point(point)?;
+        if distance > 0.0 {
+            return Ok(distance);
+        }
+
+        let depth = Zip::from(point)
+            .and(&self.lb)
+            .and(&self.ub)
+            .fold(f64::INFINITY, |acc, &p, &lb, &ub| {
+                acc.min(p - lb).min(ub - p)
+            });
+        Ok(if depth > 0.0 { -depth } else { 0.0 })
+    }
 }
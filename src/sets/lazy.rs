@@ -0,0 +1,335 @@
+#![allow(unused)]
+use super::*;
+use crate::{HPolytope, Zonotope};
+use std::rc::Rc;
+
+/// Set expression that is only evaluated through its support function.
+///
+/// Operations build a tree of nodes instead of computing the result, so chains like
+/// $\Phi X \oplus U$ cost nothing until they are queried. The support function composes along
+/// the tree: \
+/// $\rho_{A \oplus B}(d) = \rho_A(d) + \rho_B(d)$ \
+/// $\rho_{MA}(d) = \rho_A(M^\top d)$ \
+/// $\rho_{A + t}(d) = \rho_A(d) + d^\top t$ \
+/// $\rho_{\mathrm{conv}(A \cup B)}(d) = \max(\rho_A(d), \rho_B(d))$ \
+/// Intersections have no such rule and are materialized as [`HPolytope`] when queried.
+///
+/// Results are materialized with [`LazySet::evaluate`], [`LazySet::to_vpolytope`],
+/// [`LazySet::to_hpolytope`] or [`LazySet::to_zonotope`].
+/// Subexpressions are shared, so cloning is cheap.
+#[derive(Clone, Debug)]
+pub enum LazySet<S: GeoSet> {
+    Set(Rc<S>),
+    MinkowskiSum(Rc<LazySet<S>>, Rc<LazySet<S>>),
+    LinearMap(Array2<f64>, Rc<LazySet<S>>),
+    Translation(Rc<LazySet<S>>, Array1<f64>),
+    ConvexHull(Rc<LazySet<S>>, Rc<LazySet<S>>),
+    Intersection(Rc<LazySet<S>>, Rc<LazySet<S>>),
+}
+
+impl<S: GeoSet> From<S> for LazySet<S> {
+    fn from(set: S) -> Self {
+        LazySet::Set(Rc::new(set))
+    }
+}
+
+impl<S: GeoSet> LazySet<S> {
+    pub fn new(set: S) -> LazySet<S> {
+        LazySet::from(set)
+    }
+
+    /// Computes the expression with the eager operations of `S`.
+    ///
+    /// The result inherits the accuracy of these operations,
+    /// e.g. the convex hull of zonotopes is an outer approximation.
+    pub fn evaluate(&self) -> Result<S, SetOperationError> {
+        match self {
+            LazySet::Set(set) => Ok(set.as_ref().clone()),
+            LazySet::MinkowskiSum(a, b) => a.evaluate()?.minkowski_sum(&b.evaluate()?),
+            LazySet::LinearMap(mat, a) => a.evaluate()?.matmul(mat),
+            LazySet::Translation(a, vector) => a.evaluate()?.translate(vector),
+            LazySet::ConvexHull(a, b) => a.evaluate()?.convex_hull(&b.evaluate()?),
+            LazySet::Intersection(a, b) => a.evaluate()?.intersection(&b.evaluate()?),
+        }
+    }
+
+    /// Computes the expression in vertex representation from the vertices of the leaves.
+    ///
+    /// Leaves that are polytopes, i.e., intervals, zonotopes, constrained zonotopes and H- or
+    /// V-polytopes, are converted exactly. Ellipsoid leaves are replaced by the convex hull of
+    /// sampled boundary points, so the result is only an inner approximation of the expression.
+    pub fn to_vpolytope(&self) -> Result<VPolytope, SetOperationError> {
+        match self {
+            LazySet::Set(set) => VPolytope::new(set.to_vertices()?).map_err(|e| {
+                SetOperationError::DataConversionError {
+                    source: Box::new(e),
+                }
+            }),
+            LazySet::MinkowskiSum(a, b) => a.to_vpolytope()?.minkowski_sum(&b.to_vpolytope()?),
+            LazySet::LinearMap(mat, a) => a.to_vpolytope()?.matmul(mat),
+            LazySet::Translation(a, vector) => a.to_vpolytope()?.translate(vector),
+            LazySet::ConvexHull(a, b) => a.to_vpolytope()?.convex_hull(&b.to_vpolytope()?),
+            LazySet::Intersection(..) => VPolytope::new(self.to_hpolytope()?.to_vertices()?)
+                .map_err(|e| SetOperationError::DataConversionError {
+                    source: Box::new(e),
+                }),
+        }
+    }
+
+    /// Computes the expression in halfspace representation.
+    /// It is exact under the same conditions as [`LazySet::to_vpolytope`].
+    ///
+    /// Intersections stack the constraints of both operands,
+    /// all other nodes are converted from [`LazySet::to_vpolytope`].
+    pub fn to_hpolytope(&self) -> Result<HPolytope, SetOperationError> {
+        match self {
+            LazySet::Intersection(a, b) => a.to_hpolytope()?.intersection(&b.to_hpolytope()?),
+            _ => self.to_vpolytope()?.to_hpolytope(),
+        }
+    }
+
+    /// Wraps the current expression into the node returned by `node`
+    fn wrap_(&mut self, node: impl FnOnce(Rc<LazySet<S>>) -> LazySet<S>) {
+        *self = node(Rc::new(self.clone()));
+    }
+}
+
+impl<S: GeoSet + Into<Zonotope>> LazySet<S> {
+    /// Computes the expression as zonotope.
+    ///
+    /// Convex hulls are outer approximations, see [`Zonotope::convex_hull_`].
+    /// Intersections are not supported as zonotopes are not closed under intersection.
+    pub fn to_zonotope(&self) -> Result<Zonotope, SetOperationError> {
+        match self {
+            LazySet::Set(set) => Ok(set.as_ref().clone().into()),
+            LazySet::MinkowskiSum(a, b) => a.to_zonotope()?.minkowski_sum(&b.to_zonotope()?),
+            LazySet::LinearMap(mat, a) => a.to_zonotope()?.matmul(mat),
+            LazySet::Translation(a, vector) => a.to_zonotope()?.translate(vector),
+            LazySet::ConvexHull(a, b) => a.to_zonotope()?.convex_hull(&b.to_zonotope()?),
+            LazySet::Intersection(..) => Err(SetOperationError::UnsupportedOperation {
+                message: "zonotopes are not closed under intersection".to_string(),
+            }),
+        }
+    }
+}
+
+impl<S: GeoSet> GeoSet for LazySet<S> {
+    fn dim(&self) -> usize {
+        match self {
+            LazySet::Set(set) => set.dim(),
+            LazySet::LinearMap(mat, _) => mat.nrows(),
+            LazySet::MinkowskiSum(a, _)
+            | LazySet::Translation(a, _)
+            | LazySet::ConvexHull(a, _)
+            | LazySet::Intersection(a, _) => a.dim(),
+        }
+    }
+
+    fn empty(&self) -> Result<bool, SetOperationError> {
+        match self {
+            LazySet::Set(set) => set.empty(),
+            LazySet::MinkowskiSum(a, b) => Ok(a.empty()? || b.empty()?),
+            LazySet::LinearMap(_, a) | LazySet::Translation(a, _) => a.empty(),
+            LazySet::ConvexHull(a, b) => Ok(a.empty()? && b.empty()?),
+            LazySet::Intersection(..) => match self.to_hpolytope() {
+                Ok(polytope) => polytope.empty(),
+                Err(SetOperationError::EmptySet) => Ok(true),
+                Err(e) => Err(e),
+            },
+        }
+    }
+
+    fn degenerate(&self) -> bool {
+        self.to_vpolytope()
+            .map_or(true, |polytope| polytope.degenerate())
+    }
+
+    fn from_unit_box(dim: usize) -> Self {
+        LazySet::from(S::from_unit_box(dim))
+    }
+
+    fn to_vertices(&self) -> Result<Array2<f64>, SetOperationError> {
+        self.to_vpolytope()?.to_vertices()
+    }
+
+    /// Centroid of the vertices
+    fn center(&self) -> Result<Array1<f64>, SetOperationError> {
+        self.to_vpolytope()?.center()
+    }
+
+    fn support_function(
+        &self,
+        direction: Array1<f64>,
+    ) -> Result<(Array1<f64>, f64), SetOperationError> {
+        self._check_operand_dim(direction.dim())?;
+
+        match self {
+            LazySet::Set(set) => set.support_function(direction),
+            LazySet::MinkowskiSum(a, b) => {
+                let (vector_a, value_a) = a.support_function(direction.clone())?;
+                let (vector_b, value_b) = b.support_function(direction)?;
+                Ok((vector_a + vector_b, value_a + value_b))
+            }
+            LazySet::LinearMap(mat, a) => {
+                let (vector, value) = a.support_function(mat.t().dot(&direction))?;
+                Ok((mat.dot(&vector), value))
+            }
+            LazySet::Translation(a, vector) => {
+                let (support_vector, value) = a.support_function(direction.clone())?;
+                Ok((support_vector + vector, value + direction.dot(vector)))
+            }
+            LazySet::ConvexHull(a, b) => {
+                let support_a = a.support_function(direction.clone())?;
+                let support_b = b.support_function(direction)?;
+                Ok(if support_a.1 >= support_b.1 {
+                    support_a
+                } else {
+                    support_b
+                })
+            }
+            LazySet::Intersection(..) => self.to_hpolytope()?.support_function(direction),
+        }
+    }
+
+    fn volume(&self) -> Result<f64, SetOperationError> {
+        self.to_vpolytope()?.volume()
+    }
+
+    fn contains_point(&self, point: &Array1<f64>) -> Result<bool, SetOperationError> {
+        self._check_operand_dim(point.dim())?;
+
+        match self {
+            LazySet::Set(set) => set.contains_point(point),
+            LazySet::Translation(a, vector) => a.contains_point(&(point - vector)),
+            LazySet::Intersection(a, b) => Ok(a.contains_point(point)? && b.contains_point(point)?),
+            _ => self.to_vpolytope()?.contains_point(point),
+        }
+    }
+
+    fn minkowski_sum_(&mut self, other: &Self) -> Result<(), SetOperationError> {
+        self._check_operand_dim(other.dim())?;
+        self.wrap_(|a| LazySet::MinkowskiSum(a, Rc::new(other.clone())));
+        Ok(())
+    }
+
+    fn matmul_(&mut self, mat: &Array2<f64>) -> Result<(), SetOperationError> {
+        self._check_operand_dim(mat.ncols())?;
+        self.wrap_(|a| LazySet::LinearMap(mat.clone(), a));
+        Ok(())
+    }
+
+    fn translate_(&mut self, vector: &Array1<f64>) -> Result<(), SetOperationError> {
+        self._check_operand_dim(vector.dim())?;
+        self.wrap_(|a| LazySet::Translation(a, vector.clone()));
+        Ok(())
+    }
+
+    /// Disjointness is decided from the support functions before the node is added
+    fn intersection_(&mut self, other: &Self) -> Result<(), SetOperationError> {
+        self._check_operand_dim(other.dim())?;
+        if self.is_disjoint(other)?.is_some() {
+            return Err(SetOperationError::EmptySet);
+        }
+        self.wrap_(|a| LazySet::Intersection(a, Rc::new(other.clone())));
+        Ok(())
+    }
+
+    /// Materializes the expression with [`LazySet::evaluate`],
+    /// as the support function of the difference does not compose.
+    fn pontryagin_difference_<T: GeoSet>(&mut self, other: &T) -> Result<(), SetOperationError> {
+        *self = LazySet::from(self.evaluate()?.pontryagin_difference(other)?);
+        Ok(())
+    }
+
+    /// $A \times B = \begin{bmatrix} I \\ 0 \end{bmatrix} A \oplus
+    /// \begin{bmatrix} 0 \\ I \end{bmatrix} B$
+    fn cartesian_product_(&mut self, other: &Self) -> Result<(), SetOperationError> {
+        let (n, m) = (self.dim(), other.dim());
+        let embed_self = concatenate![Axis(0), Array2::eye(n), Array2::zeros((m, n))];
+        let embed_other = concatenate![Axis(0), Array2::zeros((n, m)), Array2::eye(m)];
+
+        self.wrap_(|a| {
+            LazySet::MinkowskiSum(
+                Rc::new(LazySet::LinearMap(embed_self, a)),
+                Rc::new(LazySet::LinearMap(embed_other, Rc::new(other.clone()))),
+            )
+        });
+        Ok(())
+    }
+
+    /// Linear map with the rows `dims` of the identity
+    fn project_(&mut self, dims: &[usize]) -> Result<(), SetOperationError> {
+        self._check_projection_dims(dims)?;
+        let selection = Array2::<f64>::eye(self.dim()).select(Axis(0), dims);
+        self.wrap_(|a| LazySet::LinearMap(selection, a));
+        Ok(())
+    }
+
+    fn convex_hull_(&mut self, other: &Self) -> Result<(), SetOperationError> {
+        self._check_operand_dim(other.dim())?;
+        self.wrap_(|a| LazySet::ConvexHull(a, Rc::new(other.clone())));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Interval;
+
+    #[test]
+    fn test_lazy_support_function_matches_evaluation() {
+        let x = LazySet::new(Zonotope::from_random(2, 4, false).unwrap());
+        let u = LazySet::new(Zonotope::from(Interval::from_unit_box(2)));
+        let phi = array![[0.9, -0.3], [0.2, 1.1]];
+
+        let lazy = x
+            .matmul(&phi)
+            .unwrap()
+            .minkowski_sum(&u)
+            .unwrap()
+            .translate(&array![1.0, -2.0])
+            .unwrap();
+        let eager = lazy.to_zonotope().unwrap();
+
+        for direction in [array![1.0, 0.0], array![-0.3, 0.7], array![0.0, -1.0]] {
+            let (vector, value) = lazy.support_function(direction.clone()).unwrap();
+            let (_, expected) = eager.support_function(direction.clone()).unwrap();
+            assert!((value - expected).abs() < 1e-9);
+            assert!((vector.dot(&direction) - value).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_lazy_convex_hull_and_intersection() {
+        let a = LazySet::new(Interval::from_unit_box(2));
+        let b = a.translate(&array![1.0, 0.0]).unwrap();
+
+        let hull = a.convex_hull(&b).unwrap();
+        assert!((hull.support_function(array![1.0, 0.0]).unwrap().1 - 2.0).abs() < 1e-9);
+        assert!((hull.volume().unwrap() - 6.0).abs() < 1e-9);
+
+        let intersection = a.intersection(&b).unwrap();
+        assert!((intersection.support_function(array![-1.0, 0.0]).unwrap().1).abs() < 1e-9);
+        assert!((intersection.volume().unwrap() - 2.0).abs() < 1e-9);
+        assert!(intersection.contains_point(&array![0.5, 0.5]).unwrap());
+        assert!(!intersection.contains_point(&array![-0.5, 0.5]).unwrap());
+
+        assert!(matches!(
+            intersection.to_zonotope(),
+            Err(SetOperationError::UnsupportedOperation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_lazy_operations_share_operands() {
+        let a = LazySet::new(Interval::from_unit_box(2));
+        let sum = a.minkowski_sum(&a).unwrap();
+
+        match &sum {
+            LazySet::MinkowskiSum(left, right) => assert!(matches!(**left, LazySet::Set(_))),
+            _ => panic!("expected a Minkowski sum node"),
+        }
+        assert!((sum.support_function(array![1.0, 1.0]).unwrap().1 - 4.0).abs() < 1e-9);
+    }
+}
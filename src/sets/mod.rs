@@ -1,3 +1,4 @@
+use good_lp::{Expression, Solution, SolverModel, default_solver, variable, variables};
 use ndarray::concatenate;
 use ndarray::prelude::*;
 use plotly::Layout;
@@ -5,14 +6,36 @@ use plotly::Trace;
 use plotly::common::Mode;
 use plotly::{Plot, Scatter};
 
+use self::affine_hull::AffineHull;
 use self::errors::SetOperationError;
+use self::hpolytope::HPolytope;
+use self::interval::Interval;
+use self::vpolytope::VPolytope;
 
+pub mod affine_hull;
+pub mod constrained_zonotope;
+pub mod containment;
+pub mod ellipsoid;
 pub mod errors;
+pub mod hausdorff;
 pub mod hpolytope;
 pub mod interval;
+pub mod lazy;
+pub mod template;
 pub mod vpolytope;
 pub mod zonotope;
 
+/// Maximum number of cutting planes added by [`GeoSet::is_disjoint`]
+const MAX_SEPARATION_ITERATIONS: usize = 500;
+
+/// Hyperplane $\{x : a^\top x = b\}$ with unit normal $a$ that separates two sets, such that
+/// the first set lies in $a^\top x < b$ and the second set in $a^\top x > b$.
+#[derive(Clone, Debug)]
+pub struct SeparatingHyperplane {
+    pub normal: Array1<f64>,
+    pub offset: f64,
+}
+
 pub trait GeoSet: Sized + Clone {
     fn dim(&self) -> usize;
     fn empty(&self) -> Result<bool, SetOperationError>;
@@ -34,6 +57,21 @@ pub trait GeoSet: Sized + Clone {
     fn minkowski_sum_(&mut self, other: &Self) -> Result<(), SetOperationError>;
     fn matmul_(&mut self, mat: &Array2<f64>) -> Result<(), SetOperationError>;
     fn translate_(&mut self, vector: &Array1<f64>) -> Result<(), SetOperationError>;
+    /// Intersects the set with `other`.
+    /// Returns [`SetOperationError::EmptySet`] if the intersection is empty.
+    fn intersection_(&mut self, other: &Self) -> Result<(), SetOperationError>;
+    /// Computes the Pontryagin difference $\{x : x \oplus \text{other} \subseteq \text{self}\}$.
+    /// Representations that are not closed under this operation return an inner approximation.
+    /// Returns [`SetOperationError::EmptySet`] if the difference is empty.
+    fn pontryagin_difference_<S: GeoSet>(&mut self, other: &S) -> Result<(), SetOperationError>;
+    /// Computes the Cartesian product $\text{self} \times \text{other}$, whose dimension is
+    /// the sum of both dimensions.
+    fn cartesian_product_(&mut self, other: &Self) -> Result<(), SetOperationError>;
+    /// Projects the set onto the coordinates `dims`, in the given order.
+    fn project_(&mut self, dims: &[usize]) -> Result<(), SetOperationError>;
+    /// Computes the convex hull of $\text{self} \cup \text{other}$.
+    /// Representations that are not closed under this operation return an outer approximation.
+    fn convex_hull_(&mut self, other: &Self) -> Result<(), SetOperationError>;
 
     fn minkowski_sum(&self, other: &Self) -> Result<Self, SetOperationError> {
         let mut copy = self.clone();
@@ -50,8 +88,251 @@ pub trait GeoSet: Sized + Clone {
         copy.translate_(vector)?;
         Ok(copy)
     }
+    /// Applies $x \mapsto M x + v$, where the $m \times n$ matrix $M$ maps the set to $m$
+    /// dimensions.
+    fn affine_map_(
+        &mut self,
+        mat: &Array2<f64>,
+        vector: &Array1<f64>,
+    ) -> Result<(), SetOperationError> {
+        self._check_operand_dim(mat.ncols())?;
+        if vector.dim() != mat.nrows() {
+            return Err(SetOperationError::DimensionMismatch {
+                expected: mat.nrows(),
+                got: vector.dim(),
+            });
+        }
+
+        self.matmul_(mat)?;
+        self.translate_(vector)
+    }
+    fn affine_map(
+        &self,
+        mat: &Array2<f64>,
+        vector: &Array1<f64>,
+    ) -> Result<Self, SetOperationError> {
+        let mut copy = self.clone();
+        copy.affine_map_(mat, vector)?;
+        Ok(copy)
+    }
+    fn intersection(&self, other: &Self) -> Result<Self, SetOperationError> {
+        let mut copy = self.clone();
+        copy.intersection_(other)?;
+        Ok(copy)
+    }
+    fn cartesian_product(&self, other: &Self) -> Result<Self, SetOperationError> {
+        let mut copy = self.clone();
+        copy.cartesian_product_(other)?;
+        Ok(copy)
+    }
+    fn project(&self, dims: &[usize]) -> Result<Self, SetOperationError> {
+        let mut copy = self.clone();
+        copy.project_(dims)?;
+        Ok(copy)
+    }
+    fn convex_hull(&self, other: &Self) -> Result<Self, SetOperationError> {
+        let mut copy = self.clone();
+        copy.convex_hull_(other)?;
+        Ok(copy)
+    }
+    /// Computes the convex hull of all `sets`.
+    fn convex_hull_many(sets: &[Self]) -> Result<Self, SetOperationError> {
+        let mut hull = Self::_first_of_many(sets)?;
+        for set in &sets[1..] {
+            hull.convex_hull_(set)?;
+        }
+        Ok(hull)
+    }
+    #[doc(alias = "minkowski_difference")]
+    fn pontryagin_difference<S: GeoSet>(&self, other: &S) -> Result<Self, SetOperationError> {
+        let mut copy = self.clone();
+        copy.pontryagin_difference_(other)?;
+        Ok(copy)
+    }
 
     // Generic implementations
+    /// Computes the tightest interval containing the set from the support functions
+    /// along $\pm e_i$.
+    #[doc(alias = "bounding_box")]
+    fn interval_hull(&self) -> Result<Interval, SetOperationError> {
+        let dim = self.dim();
+        let mut lb = Array1::zeros(dim);
+        let mut ub = Array1::zeros(dim);
+
+        for i in 0..dim {
+            let mut direction = Array1::zeros(dim);
+            direction[i] = 1.0;
+
+            let (_, upper) = self.support_function(direction.clone())?;
+            let (_, lower) = self.support_function(-direction)?;
+            lb[i] = -lower;
+            // Guard against round-off for sets that are flat in this dimension
+            ub[i] = upper.max(-lower);
+        }
+
+        Interval::new(lb, ub).map_err(|e| SetOperationError::DataConversionError {
+            source: Box::new(e),
+        })
+    }
+
+    /// Computes the template overapproximation $\{x : d_i^\top x \leq \rho(d_i)\}$
+    /// for the directions $d_i$ given as rows, see [`template`] for common templates.
+    ///
+    /// Only the support function is evaluated, so this is cheap for zonotopes with many
+    /// generators and for [`lazy::LazySet`] expressions.
+    /// Directions along which the set is unbounded are dropped.
+    #[allow(non_snake_case)]
+    fn overapproximate_template(
+        &self,
+        directions: &Array2<f64>,
+    ) -> Result<HPolytope, SetOperationError> {
+        self._check_operand_dim(directions.ncols())?;
+
+        let mut rows = Vec::new();
+        let mut values = Vec::new();
+        for (i, direction) in directions.outer_iter().enumerate() {
+            let (_, value) = self.support_function(direction.to_owned())?;
+            if value.is_finite() {
+                rows.push(i);
+                values.push(value);
+            }
+        }
+
+        let A = directions.select(Axis(0), &rows);
+        HPolytope::new(A, Array1::from(values)).map_err(|e| {
+            SetOperationError::DataConversionError {
+                source: Box::new(e),
+            }
+        })
+    }
+
+    /// Computes the smallest affine subspace containing the set from its vertices.
+    fn affine_hull(&self) -> Result<AffineHull, SetOperationError> {
+        AffineHull::from_points(&self.to_vertices()?)
+    }
+
+    /// Dimension of the affine hull, smaller than [`GeoSet::dim`] for flat sets.
+    fn relative_dim(&self) -> Result<usize, SetOperationError> {
+        Ok(self.affine_hull()?.dim())
+    }
+
+    /// Checks whether the sets are disjoint and returns a separating hyperplane as certificate.
+    /// Touching sets are not disjoint.
+    ///
+    /// The sets are disjoint if and only if $\rho_{self}(d) + \rho_{other}(-d) < 0$ for some $d$.
+    /// The convex function is minimized by cutting planes: the support vectors collected so far
+    /// give the lower bound \
+    /// $\min s_1 + s_2$ \
+    /// $\text{subject to } s_1 \geq v_k^\top d, s_2 \geq -w_k^\top d, d \in [-1, 1]^n$ \
+    /// and the support vectors along the minimizer are added until the bounds decide the sign.
+    /// For polytopes this terminates after finitely many steps.
+    /// `None` is only returned once the lower bound proves that the sets intersect, and an error
+    /// is returned if the bounds have not decided the sign after `MAX_SEPARATION_ITERATIONS`
+    /// cutting planes.
+    fn is_disjoint<S: GeoSet>(
+        &self,
+        other: &S,
+    ) -> Result<Option<SeparatingHyperplane>, SetOperationError> {
+        self._check_operand_dim(other.dim())?;
+        let dim = self.dim();
+        let tol = 1e-9;
+
+        // Initial cuts along the coordinate axes
+        let mut cuts_self: Vec<Array1<f64>> = Vec::new();
+        let mut cuts_other: Vec<Array1<f64>> = Vec::new();
+        for i in 0..dim {
+            for sign in [1.0, -1.0] {
+                let mut direction = Array1::zeros(dim);
+                direction[i] = sign;
+                cuts_self.push(self.support_function(direction.clone())?.0);
+                cuts_other.push(other.support_function(direction)?.0);
+            }
+        }
+
+        for _ in 0..MAX_SEPARATION_ITERATIONS {
+            let mut vars = variables!();
+            let d: Vec<_> = (0..dim)
+                .map(|_| vars.add(variable().min(-1.0).max(1.0)))
+                .collect();
+            let s1 = vars.add(variable());
+            let s2 = vars.add(variable());
+
+            let mut problem = vars.minimise(s1 + s2).using(default_solver);
+            for v in &cuts_self {
+                let expr: Expression = v.iter().zip(&d).map(|(v_i, d_i)| *v_i * *d_i).sum();
+                problem = problem.with((s1 - expr).geq(0.0));
+            }
+            for w in &cuts_other {
+                let expr: Expression = w.iter().zip(&d).map(|(w_i, d_i)| *w_i * *d_i).sum();
+                problem = problem.with((s2 + expr).geq(0.0));
+            }
+
+            let solution =
+                problem
+                    .solve()
+                    .map_err(|e| SetOperationError::InfeasibleOptimization {
+                        source: Box::new(e),
+                    })?;
+            let lower = solution.value(s1) + solution.value(s2);
+            if lower >= -tol {
+                return Ok(None);
+            }
+
+            let direction: Array1<f64> = d.iter().map(|&d_i| solution.value(d_i)).collect();
+            let (v, upper_self) = self.support_function(direction.clone())?;
+            let (w, upper_other) = other.support_function(-&direction)?;
+
+            if upper_self + upper_other < -tol {
+                let norm = direction.dot(&direction).sqrt();
+                return Ok(Some(SeparatingHyperplane {
+                    normal: direction / norm,
+                    offset: (upper_self - upper_other) / (2. * norm),
+                }));
+            }
+
+            cuts_self.push(v);
+            cuts_other.push(w);
+        }
+
+        Err(SetOperationError::UnsupportedOperation {
+            message: format!(
+                "disjointness undecided after {} cutting planes",
+                MAX_SEPARATION_ITERATIONS
+            ),
+        })
+    }
+
+    /// Computes the point of the set closest to `point` in the Euclidean norm.
+    ///
+    /// By default runs GJK between the set and the point, which only needs the support
+    /// function. For polytopes this terminates after finitely many steps.
+    fn closest_point(&self, point: &Array1<f64>) -> Result<Array1<f64>, SetOperationError> {
+        let proximity = crate::gjk::distance(self, &VPolytope::from_point(point))?;
+        Ok(proximity.point_a)
+    }
+
+    /// Computes the Euclidean distance from `point` to the set, zero if the point is contained.
+    fn distance_to_point(&self, point: &Array1<f64>) -> Result<f64, SetOperationError> {
+        let diff = point - &self.closest_point(point)?;
+        Ok(diff.dot(&diff).sqrt())
+    }
+
+    /// Computes the signed distance from `point` to the boundary of the set,
+    /// negative if the point lies in the interior.
+    ///
+    /// Outside the set this is the distance to the set. Inside the set it is
+    /// $-\min_{\|d\|_2 = 1} \rho(d) - d^\top p$, computed by the expanding polytope algorithm.
+    fn signed_distance(&self, point: &Array1<f64>) -> Result<f64, SetOperationError> {
+        let distance = self.distance_to_point(point)?;
+        if distance > 1e-9 {
+            return Ok(distance);
+        }
+        match crate::gjk::penetration_depth(self, &VPolytope::from_point(point))? {
+            Some(penetration) if penetration.depth > 0.0 => Ok(-penetration.depth),
+            _ => Ok(0.0),
+        }
+    }
+
     fn create_trace(
         &self,
         dim: (usize, usize),
@@ -120,4 +401,30 @@ pub trait GeoSet: Sized + Clone {
         }
         Ok(())
     }
+
+    fn _first_of_many(sets: &[Self]) -> Result<Self, SetOperationError> {
+        let first = sets
+            .first()
+            .ok_or_else(|| SetOperationError::UnsupportedOperation {
+                message: "at least one set is required".to_string(),
+            })?;
+        for set in &sets[1..] {
+            first._check_operand_dim(set.dim())?;
+        }
+        Ok(first.clone())
+    }
+
+    fn _check_projection_dims(&self, dims: &[usize]) -> Result<(), SetOperationError> {
+        let unique = dims.iter().collect::<std::collections::HashSet<_>>().len() == dims.len();
+        if dims.is_empty() || !unique || dims.iter().any(|&i| i >= self.dim()) {
+            return Err(SetOperationError::UnsupportedOperation {
+                message: format!(
+                    "projection dimensions {:?} must be unique and smaller than {}",
+                    dims,
+                    self.dim()
+                ),
+            });
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,258 @@
+#![allow(unused)]
+use super::*;
+use itertools::Itertools;
+
+/// Maximum number of directions added by [`polar_directions`]
+const MAX_POLAR_DIRECTIONS: usize = 10_000;
+
+/// Directions $\pm e_i$, one per row.
+/// The template overapproximation is the interval hull.
+pub fn box_directions(dim: usize) -> Array2<f64> {
+    concatenate![
+        Axis(0),
+        Array2::<f64>::eye(dim).view(),
+        (-Array2::<f64>::eye(dim)).view()
+    ]
+}
+
+/// Directions $\pm e_i$ and $(\pm e_i \pm e_j) / \sqrt{2}$ for $i < j$, one per row.
+/// In two dimensions the template overapproximation is an octagon.
+pub fn octagon_directions(dim: usize) -> Array2<f64> {
+    let mut directions = box_directions(dim)
+        .outer_iter()
+        .map(|row| row.to_owned())
+        .collect_vec();
+
+    for (i, j) in (0..dim).tuple_combinations() {
+        for (sign_i, sign_j) in [(1.0, 1.0), (1.0, -1.0), (-1.0, 1.0), (-1.0, -1.0)] {
+            let mut direction = Array1::zeros(dim);
+            direction[i] = sign_i;
+            direction[j] = sign_j;
+            directions.push(direction / 2.0_f64.sqrt());
+        }
+    }
+
+    stack_rows(&directions, dim)
+}
+
+/// Unit directions such that every unit vector lies within distance `epsilon` of one of them.
+///
+/// The faces of the cube $[-1, 1]^n$ are covered by a grid with spacing
+/// $h \leq \epsilon / \sqrt{n - 1}$ and the grid points are projected onto the sphere.
+/// A grid point is at most $\epsilon / 2$ away from any point on its face, and the projection
+/// onto the sphere at most doubles this distance.
+/// The number of directions grows like $2n (2 \sqrt{n - 1} / \epsilon)^{n - 1}$.
+pub fn spherical_directions(dim: usize, epsilon: f64) -> Result<Array2<f64>, SetOperationError> {
+    if epsilon <= 0.0 {
+        return Err(SetOperationError::UnsupportedOperation {
+            message: format!("epsilon must be positive, got {}", epsilon),
+        });
+    }
+    if dim <= 1 {
+        return Ok(box_directions(dim));
+    }
+
+    let n_intervals = ((2.0 * ((dim - 1) as f64).sqrt() / epsilon).ceil() as usize).max(1);
+    let coordinate = |k: usize| -1.0 + 2.0 * k as f64 / n_intervals as f64;
+
+    let mut directions = Vec::new();
+    for face in 0..dim {
+        let free_dims = (0..dim).filter(|&j| j != face).collect_vec();
+
+        for grid_point in (0..free_dims.len())
+            .map(|_| 0..=n_intervals)
+            .multi_cartesian_product()
+        {
+            // Points on an edge with a face of lower index were already added
+            let on_previous_face = free_dims
+                .iter()
+                .zip(&grid_point)
+                .any(|(&j, &k)| j < face && (k == 0 || k == n_intervals));
+            if on_previous_face {
+                continue;
+            }
+
+            for sign in [1.0, -1.0] {
+                let mut direction = Array1::zeros(dim);
+                direction[face] = sign;
+                for (&j, &k) in free_dims.iter().zip(&grid_point) {
+                    direction[j] = coordinate(k);
+                }
+                let norm = direction.dot(&direction).sqrt();
+                directions.push(direction / norm);
+            }
+        }
+    }
+
+    Ok(stack_rows(&directions, dim))
+}
+
+/// Refines a template for a two-dimensional set until its overapproximation is at most `tol`
+/// away from the set in the Hausdorff distance.
+///
+/// Starting from the box template, the support vectors $v_a, v_b$ of two adjacent directions
+/// span an edge of an inner approximation, and the intersection of their support lines is
+/// a vertex of the outer approximation. If this vertex is farther than `tol` from the edge,
+/// the outer normal of the edge is added between both directions.
+/// The refinement stops after [`MAX_POLAR_DIRECTIONS`] directions.
+pub fn polar_directions<S: GeoSet>(set: &S, tol: f64) -> Result<Array2<f64>, SetOperationError> {
+    if set.dim() != 2 {
+        return Err(SetOperationError::UnsupportedOperation {
+            message: "polar refinement is only available in two dimensions".to_string(),
+        });
+    }
+
+    let support = |direction: Array1<f64>| -> Result<_, SetOperationError> {
+        let (vector, value) = set.support_function(direction.clone())?;
+        Ok((direction, vector, value))
+    };
+
+    // Counterclockwise order, the last edge closes the polygon
+    let initial = [
+        array![1.0, 0.0],
+        array![0.0, 1.0],
+        array![-1.0, 0.0],
+        array![0.0, -1.0],
+    ]
+    .into_iter()
+    .map(support)
+    .collect::<Result<Vec<_>, _>>()?;
+
+    let mut directions = Vec::new();
+    let mut pending = (0..4)
+        .rev()
+        .map(|i| (initial[i].clone(), initial[(i + 1) % 4].clone()))
+        .collect_vec();
+
+    while let Some((a, b)) = pending.pop() {
+        let (d_a, v_a, rho_a) = &a;
+        let (d_b, v_b, rho_b) = &b;
+
+        let edge = v_b - v_a;
+        let corner = line_intersection(d_a, *rho_a, d_b, *rho_b);
+        let converged = match corner {
+            Some(corner) => segment_distance(&corner, v_a, v_b) <= tol,
+            None => true,
+        };
+
+        if converged
+            || edge.dot(&edge).sqrt() <= tol
+            || directions.len() + pending.len() >= MAX_POLAR_DIRECTIONS
+        {
+            directions.push(d_a.clone());
+            continue;
+        }
+
+        let normal = array![edge[1], -edge[0]];
+        let c = support(&normal / normal.dot(&normal).sqrt())?;
+        pending.push((c.clone(), b));
+        pending.push((a, c));
+    }
+
+    Ok(stack_rows(&directions, 2))
+}
+
+/// Intersection of the lines $d_a^\top x = \rho_a$ and $d_b^\top x = \rho_b$ in the plane
+fn line_intersection(
+    d_a: &Array1<f64>,
+    rho_a: f64,
+    d_b: &Array1<f64>,
+    rho_b: f64,
+) -> Option<Array1<f64>> {
+    let det = d_a[0] * d_b[1] - d_a[1] * d_b[0];
+    if det.abs() < 1e-12 {
+        return None;
+    }
+    Some(array![
+        (rho_a * d_b[1] - rho_b * d_a[1]) / det,
+        (d_a[0] * rho_b - d_b[0] * rho_a) / det
+    ])
+}
+
+/// Distance of `point` to the segment between `a` and `b`
+fn segment_distance(point: &Array1<f64>, a: &Array1<f64>, b: &Array1<f64>) -> f64 {
+    let edge = b - a;
+    let length_squared = edge.dot(&edge);
+    let t = if length_squared > 0.0 {
+        ((point - a).dot(&edge) / length_squared).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let diff = point - &(a + &(t * &edge));
+    diff.dot(&diff).sqrt()
+}
+
+fn stack_rows(rows: &[Array1<f64>], dim: usize) -> Array2<f64> {
+    let mut matrix = Array2::zeros((rows.len(), dim));
+    for (mut row, direction) in matrix.rows_mut().into_iter().zip(rows) {
+        row.assign(direction);
+    }
+    matrix
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Ellipsoid, Zonotope};
+
+    #[test]
+    fn test_template_sizes() {
+        assert_eq!(box_directions(3).dim(), (6, 3));
+        assert_eq!(octagon_directions(2).nrows(), 8);
+        assert_eq!(octagon_directions(3).nrows(), 18);
+    }
+
+    #[test]
+    fn test_spherical_directions_cover_sphere() {
+        let epsilon = 0.3;
+        for dim in 2..4 {
+            let directions = spherical_directions(dim, epsilon).unwrap();
+            for row in directions.outer_iter() {
+                assert!((row.dot(&row) - 1.0).abs() < 1e-12);
+            }
+
+            for sample in hausdorff::sample_directions(dim, 200).outer_iter() {
+                let distance = directions
+                    .outer_iter()
+                    .map(|d| (&d - &sample).dot(&(&d - &sample)).sqrt())
+                    .fold(f64::INFINITY, f64::min);
+                assert!(distance <= epsilon);
+            }
+        }
+        assert!(spherical_directions(2, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_polar_refinement_of_disk() {
+        let disk = Ellipsoid::from_unit_box(2);
+        let tol = 1e-3;
+
+        let directions = polar_directions(&disk, tol).unwrap();
+        let outer = disk.overapproximate_template(&directions).unwrap();
+
+        // The vertices of the outer polygon are at most tol away from the unit disk
+        for vertex in outer.to_vertices().unwrap().outer_iter() {
+            let norm = vertex.dot(&vertex).sqrt();
+            assert!(norm >= 1.0 - 1e-9 && norm <= 1.0 + tol + 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_polar_refinement_of_polygon_is_exact() {
+        let zonotope = Zonotope::from_random(2, 5, false).unwrap();
+        let outer = zonotope
+            .overapproximate_template(&polar_directions(&zonotope, 1e-9).unwrap())
+            .unwrap();
+
+        let exact = zonotope.to_vertices().unwrap();
+        let exact = VPolytope::new(exact).unwrap();
+        let approximation = VPolytope::new(outer.to_vertices().unwrap()).unwrap();
+        assert!(exact.hausdorff_distance(&approximation).unwrap() < 1e-6);
+    }
+
+    #[test]
+    fn test_polar_refinement_requires_2d() {
+        let zonotope = Zonotope::from_unit_box(3);
+        assert!(polar_directions(&zonotope, 1e-3).is_err());
+    }
+}
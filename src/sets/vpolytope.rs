@@ -1,6 +1,8 @@
 #![allow(unused)]
+use crate::HPolytope;
+use crate::cddlib_rs::compute_polytope_halfspaces;
 use crate::linalg_utils::{argmax, rank};
-use crate::qhull_wrapper::{convex_hull, convex_hull_vertices, qhull_volume};
+use crate::qhull_wrapper::{convex_hull_vertices, relative_volume};
 
 use super::*;
 use good_lp::{Expression, Solution, SolverModel, default_solver, variable, variables};
@@ -34,10 +36,21 @@ impl VPolytope {
         VPolytope::new(vertices)
     }
 
+    /// Creates the polytope consisting of a single point
+    pub fn from_point(point: &Array1<f64>) -> VPolytope {
+        VPolytope {
+            vertices: point.clone().insert_axis(Axis(0)),
+        }
+    }
+
     pub fn n_vertices(&self) -> usize {
         self.vertices.nrows()
     }
 
+    pub fn vertices(&self) -> &Array2<f64> {
+        &self.vertices
+    }
+
     pub fn compact_(&mut self) -> Result<(), SetOperationError> {
         self.vertices = convex_hull_vertices(&self.vertices)?;
         Ok(())
@@ -48,6 +61,47 @@ impl VPolytope {
         copy.compact_()?;
         Ok(copy)
     }
+
+    /// Computes the volume of the polytope relative to its affine hull,
+    /// e.g. the area of a flat polygon in $\mathbb{R}^3$.
+    ///
+    /// Full-dimensional polytopes have the same relative volume and volume.
+    /// A single point has the zero-dimensional volume one.
+    pub fn relative_volume(&self) -> Result<f64, SetOperationError> {
+        Ok(relative_volume(&self.vertices)?)
+    }
+
+    /// Computes the facet representation $Ax \leq b, A_e x = b_e$ of the polytope.
+    ///
+    /// The facets are enumerated by cddlib, so the representation is irredundant.
+    /// Rows are normalized to unit norm. The affine hull of lower-dimensional polytopes is
+    /// given by the equality constraints.
+    pub fn to_hpolytope(&self) -> Result<HPolytope, SetOperationError> {
+        let mut halfspaces = compute_polytope_halfspaces(&self.vertices)?;
+
+        let rows = halfspaces
+            .a
+            .rows_mut()
+            .into_iter()
+            .zip(halfspaces.b.iter_mut())
+            .chain(
+                halfspaces
+                    .ae
+                    .rows_mut()
+                    .into_iter()
+                    .zip(halfspaces.be.iter_mut()),
+            );
+        for (mut row, b_i) in rows {
+            let norm = row.dot(&row).sqrt();
+            row /= norm;
+            *b_i /= norm;
+        }
+
+        HPolytope::new_with_equalities(halfspaces.a, halfspaces.b, halfspaces.ae, halfspaces.be)
+            .map_err(|e| SetOperationError::DataConversionError {
+                source: Box::new(e),
+            })
+    }
 }
 
 impl GeoSet for VPolytope {
@@ -95,11 +149,7 @@ impl GeoSet for VPolytope {
         if self.degenerate() {
             return Ok(0.0);
         }
-
-        let vertices = self.to_vertices()?;
-        let qh = convex_hull(&vertices, true)?;
-
-        Ok(qhull_volume(&qh, &vertices)?)
+        self.relative_volume()
     }
 
     fn minkowski_sum_(&mut self, other: &Self) -> Result<(), SetOperationError> {
@@ -118,7 +168,7 @@ impl GeoSet for VPolytope {
     }
 
     fn matmul_(&mut self, mat: &Array2<f64>) -> Result<(), SetOperationError> {
-        self._check_operand_dim(mat.dim().0)?;
+        self._check_operand_dim(mat.dim().1)?;
         self.vertices = self.vertices.dot(&mat.t());
         Ok(())
     }
@@ -130,6 +180,70 @@ impl GeoSet for VPolytope {
         Ok(())
     }
 
+    /// Converts both polytopes to H-representation, intersects them and
+    /// enumerates the vertices of the result.
+    fn intersection_(&mut self, other: &Self) -> Result<(), SetOperationError> {
+        self._check_operand_dim(other.dim())?;
+
+        // Returns EmptySet if the polytopes do not intersect
+        let hpoly = self.to_hpolytope()?.intersection(&other.to_hpolytope()?)?;
+        self.vertices = hpoly.to_vertices()?;
+        Ok(())
+    }
+
+    /// Concatenates all pairs of vertices
+    fn cartesian_product_(&mut self, other: &Self) -> Result<(), SetOperationError> {
+        let (n, m) = (self.n_vertices(), other.n_vertices());
+        let mut vertices = Array2::zeros((n * m, self.dim() + other.dim()));
+
+        for (i, v) in self.vertices.outer_iter().enumerate() {
+            for (j, w) in other.vertices.outer_iter().enumerate() {
+                let mut row = vertices.row_mut(i * m + j);
+                row.slice_mut(s![..self.dim()]).assign(&v);
+                row.slice_mut(s![self.dim()..]).assign(&w);
+            }
+        }
+
+        self.vertices = vertices;
+        Ok(())
+    }
+
+    /// Selects the columns of the vertices, some of which may no longer be extreme
+    fn project_(&mut self, dims: &[usize]) -> Result<(), SetOperationError> {
+        self._check_projection_dims(dims)?;
+        self.vertices = self.vertices.select(Axis(1), dims);
+        Ok(())
+    }
+
+    /// Concatenates the vertices and keeps the extreme ones
+    fn convex_hull_(&mut self, other: &Self) -> Result<(), SetOperationError> {
+        self._check_operand_dim(other.dim())?;
+        self.vertices = concatenate![Axis(0), self.vertices, other.vertices];
+        self.compact_()
+    }
+
+    fn convex_hull_many(sets: &[Self]) -> Result<Self, SetOperationError> {
+        let mut hull = Self::_first_of_many(sets)?;
+        let views: Vec<_> = sets.iter().map(|set| set.vertices.view()).collect();
+        hull.vertices = ndarray::concatenate(Axis(0), &views).unwrap();
+        hull.compact_()?;
+        Ok(hull)
+    }
+
+    /// Computes the exact difference in H-representation and enumerates its vertices.
+    fn pontryagin_difference_<S: GeoSet>(&mut self, other: &S) -> Result<(), SetOperationError> {
+        self._check_operand_dim(other.dim())?;
+
+        let hpoly = self.to_hpolytope()?.pontryagin_difference(other)?;
+        self.vertices = hpoly.to_vertices()?;
+        Ok(())
+    }
+
+    /// Affine hull of the vertices without compacting them first
+    fn affine_hull(&self) -> Result<AffineHull, SetOperationError> {
+        AffineHull::from_points(&self.vertices)
+    }
+
     fn degenerate(&self) -> bool {
         if self.n_vertices() == 1 {
             return true;
@@ -138,6 +252,20 @@ impl GeoSet for VPolytope {
         rank(&mat).unwrap() < self.dim()
     }
 
+    /// Columnwise minimum and maximum of the vertices
+    fn interval_hull(&self) -> Result<Interval, SetOperationError> {
+        let lb = self
+            .vertices
+            .fold_axis(Axis(0), f64::INFINITY, |&acc, &x| acc.min(x));
+        let ub = self
+            .vertices
+            .fold_axis(Axis(0), f64::NEG_INFINITY, |&acc, &x| acc.max(x));
+
+        Interval::new(lb, ub).map_err(|e| SetOperationError::DataConversionError {
+            source: Box::new(e),
+        })
+    }
+
     /// Evaluates the feasibility of the optimization problem
     /// $\min 0$ \
     /// $\text{subject to } V \lambda = p; 1^\top \lambda = 1, \lambda \geq 0 b$ \
@@ -167,6 +295,13 @@ impl GeoSet for VPolytope {
             Err(_) => Ok(false), // infeasible → empty
         }
     }
+
+    /// Minimum norm point of the vertices shifted by the point, computed with Wolfe's algorithm
+    fn closest_point(&self, point: &Array1<f64>) -> Result<Array1<f64>, SetOperationError> {
+        self._check_operand_dim(point.dim())?;
+        let (offset, _) = crate::gjk::min_norm_point(&(&self.vertices - point))?;
+        Ok(point + &offset)
+    }
 }
 
 #[cfg(test)]
@@ -178,4 +313,42 @@ mod tests {
         let _ = VPolytope::new(Array::ones((2, 2))).unwrap();
         let _ = VPolytope::new(Array::ones((2, 5))).unwrap();
     }
+
+    #[test]
+    fn test_flat_polytope() {
+        // Square in the plane z = 1 with its center as redundant point
+        let square = VPolytope::new(array![
+            [0.0, 0.0, 1.0],
+            [1.0, 0.0, 1.0],
+            [1.0, 1.0, 1.0],
+            [0.0, 1.0, 1.0],
+            [0.5, 0.5, 1.0]
+        ])
+        .unwrap();
+
+        assert_eq!(square.relative_dim().unwrap(), 2);
+        assert_eq!(square.compact().unwrap().n_vertices(), 4);
+        assert_eq!(square.volume().unwrap(), 0.0);
+        assert!((square.relative_volume().unwrap() - 1.0).abs() < 1e-9);
+
+        let shifted = square.translate(&array![1.0, 0.0, 0.0]).unwrap();
+        let hull = square.convex_hull(&shifted).unwrap();
+        assert_eq!(hull.n_vertices(), 4);
+        assert!((hull.relative_volume().unwrap() - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_collinear_polytope() {
+        let segment =
+            VPolytope::new(array![[0.0, 0.0], [1.0, 1.0], [3.0, 3.0], [2.0, 2.0]]).unwrap();
+
+        let compact = segment.compact().unwrap();
+        assert_eq!(compact.n_vertices(), 2);
+        assert_eq!(segment.relative_dim().unwrap(), 1);
+        assert!((segment.relative_volume().unwrap() - 18.0_f64.sqrt()).abs() < 1e-9);
+
+        let point = VPolytope::from_point(&array![1.0, 2.0]);
+        assert_eq!(point.compact().unwrap().n_vertices(), 1);
+        assert_eq!(point.relative_dim().unwrap(), 0);
+    }
 }
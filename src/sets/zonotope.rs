@@ -1,11 +1,12 @@
 #![allow(unused)]
 use super::*;
-use crate::linalg_utils::{rank, sign, vector_all_close};
+use crate::HPolytope;
+use crate::linalg_utils::{block_diag, rank, sign, vector_all_close};
 use crate::qhull_wrapper::convex_hull_vertices;
 use good_lp::{Expression, Solution, SolverModel, constraint, default_solver, variable, variables};
 use itertools::Itertools;
 use ndarray::Shape;
-use ndarray_linalg::Determinant;
+use ndarray_linalg::{Determinant, SVD};
 use ndarray_rand::RandomExt;
 use ndarray_rand::rand_distr::{Exp1, Uniform};
 use qhull::Qh;
@@ -24,6 +25,20 @@ pub enum ZonotopeError {
     DimensionMismatch { g_dim: (usize, usize), c_dim: usize },
 }
 
+/// Methods for [`Zonotope::reduce_order`].
+/// All methods select the generators to reduce and enclose them by a box, which results in a
+/// sound over-approximation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReductionMethod {
+    /// Reduces the generators with the smallest $\|g\|_1 - \|g\|_\infty$ by an axis-aligned box
+    Girard,
+    /// Reduces the generators with the smallest $\|g\|_2$ by an axis-aligned box
+    Combastel,
+    /// Reduces the same generators as [`ReductionMethod::Girard`] by a box aligned with their
+    /// principal components
+    Pca,
+}
+
 #[allow(non_snake_case)]
 impl Zonotope {
     pub fn new(G: Array2<f64>, c: Array1<f64>) -> Result<Zonotope, ZonotopeError> {
@@ -66,6 +81,83 @@ impl Zonotope {
         self.G.nrows()
     }
 
+    /// Reduces the number of generators to at most `target_order` times the dimension.
+    ///
+    /// The $m - (o - 1) n$ selected generators are replaced by the $n$ generators of an
+    /// enclosing box, so the result always contains the original zonotope.
+    pub fn reduce_order_(
+        &mut self,
+        target_order: usize,
+        method: ReductionMethod,
+    ) -> Result<(), SetOperationError> {
+        if target_order == 0 {
+            return Err(SetOperationError::UnsupportedOperation {
+                message: "target order must be at least 1".to_string(),
+            });
+        }
+
+        let dim = self.dim();
+        let max_generators = target_order * dim;
+        if self.n_generators() <= max_generators {
+            return Ok(());
+        }
+
+        // Sort generators by ascending score, the first ones are reduced
+        let score = |g: ArrayView1<f64>| -> f64 {
+            match method {
+                ReductionMethod::Girard | ReductionMethod::Pca => {
+                    g.abs().sum() - g.fold(0.0_f64, |a, &b| a.max(b.abs()))
+                }
+                ReductionMethod::Combastel => g.dot(&g).sqrt(),
+            }
+        };
+        let order: Vec<usize> = (0..self.n_generators())
+            .sorted_by(|&i, &j| score(self.G.row(i)).total_cmp(&score(self.G.row(j))))
+            .collect();
+
+        let n_reduced = self.n_generators() - (max_generators - dim);
+        let G_reduced = self.G.select(Axis(0), &order[..n_reduced]);
+        let G_kept = self.G.select(Axis(0), &order[n_reduced..]);
+
+        // Orthonormal basis as rows, the box generators are its scaled rows
+        let basis = match method {
+            ReductionMethod::Girard | ReductionMethod::Combastel => Array2::eye(dim),
+            ReductionMethod::Pca => {
+                let (_, _, vt) = G_reduced.svd(false, true).map_err(|e| {
+                    SetOperationError::DataConversionError {
+                        source: Box::new(e),
+                    }
+                })?;
+                vt.unwrap()
+            }
+        };
+        let radius = G_reduced.dot(&basis.t()).abs().sum_axis(Axis(0));
+        let G_box = Array2::from_diag(&radius).dot(&basis);
+
+        // Drop the box generators that are zero
+        let nonzero: Vec<usize> = (0..dim).filter(|&i| radius[i] > 0.0).collect();
+        self.G = concatenate![Axis(0), G_kept, G_box.select(Axis(0), &nonzero)];
+        Ok(())
+    }
+
+    pub fn reduce_order(
+        &self,
+        target_order: usize,
+        method: ReductionMethod,
+    ) -> Result<Zonotope, SetOperationError> {
+        let mut copy = self.clone();
+        copy.reduce_order_(target_order, method)?;
+        Ok(copy)
+    }
+
+    pub fn G(&self) -> &Array2<f64> {
+        &self.G
+    }
+
+    pub fn c(&self) -> &Array1<f64> {
+        &self.c
+    }
+
     pub fn is_zero_centered(&self) -> bool {
         self.c.iter().all(|&x| x.abs() < 1e-9)
     }
@@ -125,6 +217,109 @@ impl Zonotope {
             Err(_) => Ok(f64::INFINITY),
         }
     }
+
+    /// Computes the exact facet representation $Ax \leq b$ of the zonotope.
+    ///
+    /// Each facet normal is orthogonal to $n-1$ linearly independent generators, so the
+    /// candidates are the generalized cross products of all $(n-1)$-subsets of generators.
+    /// Degenerate zonotopes are handled within the span of their generators and fixed by
+    /// equality constraints along its orthogonal complement.
+    pub fn to_hpolytope(&self) -> Result<HPolytope, SetOperationError> {
+        let dim = self.dim();
+        let tol = 1e-9;
+
+        // Zero generators do not contribute to any facet
+        let nonzero: Vec<usize> = (0..self.n_generators())
+            .filter(|&i| self.G.row(i).iter().any(|g| g.abs() > tol))
+            .collect();
+        let G = self.G.select(Axis(0), &nonzero);
+
+        // Orthonormal basis of the generator span (first rows of vt) and its complement
+        let (span_dim, vt) = if G.nrows() == 0 {
+            (0, Array2::eye(dim))
+        } else {
+            let (_, s, vt) =
+                G.svd(false, true)
+                    .map_err(|e| SetOperationError::DataConversionError {
+                        source: Box::new(e),
+                    })?;
+            (s.iter().filter(|&&value| value > tol).count(), vt.unwrap())
+        };
+
+        // Work in the coordinates of the span only if the zonotope is degenerate
+        let basis = if span_dim == dim {
+            Array2::eye(dim)
+        } else {
+            vt.slice(s![..span_dim, ..]).to_owned()
+        };
+        let G_span = G.dot(&basis.t());
+
+        let mut normals: Vec<Array1<f64>> = Vec::new();
+        if span_dim == 1 {
+            normals.push(array![1.0]);
+        } else if span_dim > 1 {
+            for comb in (0..G_span.nrows()).combinations(span_dim - 1) {
+                let submatrix = G_span.select(Axis(0), &comb);
+
+                // Generalized cross product of the selected generators
+                let mut normal: Array1<f64> = (0..span_dim)
+                    .map(|k| {
+                        let mut columns: Vec<usize> = (0..span_dim).collect();
+                        columns.remove(k);
+                        let minor = submatrix.select(Axis(1), &columns);
+                        let sign = if k % 2 == 0 { 1.0 } else { -1.0 };
+                        sign * minor.det().unwrap_or(0.0)
+                    })
+                    .collect();
+
+                let norm = normal.dot(&normal).sqrt();
+                if norm < tol {
+                    // Linearly dependent generators
+                    continue;
+                }
+                normal /= norm;
+
+                // Orient the normal so that duplicates can be detected
+                if let Some(first) = normal.iter().find(|x| x.abs() > tol)
+                    && *first < 0.0
+                {
+                    normal = -normal;
+                }
+
+                if !normals
+                    .iter()
+                    .any(|other| vector_all_close(other, &normal, 1e-9))
+                {
+                    normals.push(normal);
+                }
+            }
+        }
+
+        let n_rows = 2 * normals.len();
+        let mut A = Array2::<f64>::zeros((n_rows, dim));
+        let mut b = Array1::<f64>::zeros(n_rows);
+
+        for (i, normal) in normals.iter().enumerate() {
+            let a = basis.t().dot(normal);
+            let offset = a.dot(&self.c);
+            let delta = G.dot(&a).abs().sum();
+
+            A.row_mut(2 * i).assign(&a);
+            b[2 * i] = offset + delta;
+            A.row_mut(2 * i + 1).assign(&(-&a));
+            b[2 * i + 1] = -offset + delta;
+        }
+
+        // Equality constraints along the directions that are not spanned by the generators
+        let Ae = vt.slice(s![span_dim.., ..]).to_owned();
+        let be = Ae.dot(&self.c);
+
+        HPolytope::new_with_equalities(A, b, Ae, be).map_err(|e| {
+            SetOperationError::DataConversionError {
+                source: Box::new(e),
+            }
+        })
+    }
 }
 
 #[allow(non_snake_case)]
@@ -206,7 +401,7 @@ impl GeoSet for Zonotope {
     }
 
     fn matmul_(&mut self, mat: &Array2<f64>) -> Result<(), SetOperationError> {
-        self._check_operand_dim(mat.dim().0)?;
+        self._check_operand_dim(mat.dim().1)?;
         self.c = mat.dot(&self.c);
         self.G = self.G.dot(&mat.t());
         Ok(())
@@ -218,13 +413,133 @@ impl GeoSet for Zonotope {
         Ok(())
     }
 
+    fn intersection_(&mut self, other: &Self) -> Result<(), SetOperationError> {
+        Err(SetOperationError::UnsupportedOperation {
+            message: "zonotopes are not closed under intersection".to_string(),
+        })
+    }
+
+    /// Block-diagonal generators $G = \mathrm{diag}(G_1, G_2)$, $c = [c_1; c_2]$
+    fn cartesian_product_(&mut self, other: &Self) -> Result<(), SetOperationError> {
+        self.G = block_diag(&self.G, &other.G);
+        self.c = concatenate![Axis(0), self.c, other.c];
+        Ok(())
+    }
+
+    fn project_(&mut self, dims: &[usize]) -> Result<(), SetOperationError> {
+        self._check_projection_dims(dims)?;
+        self.G = self.G.select(Axis(1), dims);
+        self.c = self.c.select(Axis(0), dims);
+        Ok(())
+    }
+
+    /// Outer approximation that pairs the generators $g_i$ and $h_i$ of both zonotopes,
+    /// padding the shorter list with zeros: \
+    /// $c = \frac{c_1 + c_2}{2}$, generators $\frac{g_i + h_i}{2}$, $\frac{g_i - h_i}{2}$ and
+    /// $\frac{c_1 - c_2}{2}$.
+    fn convex_hull_(&mut self, other: &Self) -> Result<(), SetOperationError> {
+        self._check_operand_dim(other.dim())?;
+
+        let m = self.n_generators().max(other.n_generators());
+        let mut G1 = Array2::zeros((m, self.dim()));
+        let mut G2 = Array2::zeros((m, self.dim()));
+        G1.slice_mut(s![..self.n_generators(), ..]).assign(&self.G);
+        G2.slice_mut(s![..other.n_generators(), ..])
+            .assign(&other.G);
+
+        let center_difference = ((&self.c - &other.c) / 2.).insert_axis(Axis(0));
+        self.G = concatenate![
+            Axis(0),
+            (&G1 + &G2) / 2.,
+            (&G1 - &G2) / 2.,
+            center_difference
+        ];
+        self.c = (&self.c + &other.c) / 2.;
+        Ok(())
+    }
+
+    /// Computes an inner approximation by scaling the generators.
+    /// With the exact difference $\{x : Hx \leq f - \rho_{other}(H)\}$ of the facet
+    /// representation, the LP \
+    /// $\max \sum_i \alpha_i \|g_i\|_2$ \
+    /// $\text{subject to } h_j^\top c' + \sum_i \alpha_i |h_j^\top g_i| \leq f_j - \rho_{other}(h_j), \alpha \in [0, 1]^m$ \
+    /// yields the largest zonotope with center $c'$ and generators $\alpha_i g_i$ in the difference.
+    fn pontryagin_difference_<S: GeoSet>(&mut self, other: &S) -> Result<(), SetOperationError> {
+        self._check_operand_dim(other.dim())?;
+
+        let difference = self.to_hpolytope()?.pontryagin_difference(other)?;
+
+        let mut vars = variables!();
+        let center: Vec<_> = (0..self.dim()).map(|_| vars.add(variable())).collect();
+        let alpha: Vec<_> = (0..self.n_generators())
+            .map(|_| vars.add(variable().min(0.0).max(1.0)))
+            .collect();
+
+        let objective: Expression = self
+            .G
+            .outer_iter()
+            .zip(&alpha)
+            .map(|(g, alpha_i)| g.dot(&g).sqrt() * *alpha_i)
+            .sum();
+        let mut problem = vars.maximise(objective).using(default_solver);
+
+        for (h, &f) in difference.A().outer_iter().zip(difference.b().iter()) {
+            let offset: Expression = h.iter().zip(&center).map(|(h_i, c_i)| *h_i * *c_i).sum();
+            let radius: Expression = self
+                .G
+                .dot(&h)
+                .iter()
+                .zip(&alpha)
+                .map(|(p_i, alpha_i)| p_i.abs() * *alpha_i)
+                .sum();
+            problem = problem.with((offset + radius).leq(f));
+        }
+
+        let solution = problem
+            .solve()
+            .map_err(|e| SetOperationError::InfeasibleOptimization {
+                source: Box::new(e),
+            })?;
+
+        let alpha: Array1<f64> = alpha.iter().map(|&a| solution.value(a)).collect();
+        let nonzero: Vec<usize> = (0..self.n_generators())
+            .filter(|&i| alpha[i] > 1e-9)
+            .collect();
+
+        self.G = Array2::from_diag(&alpha)
+            .dot(&self.G)
+            .select(Axis(0), &nonzero);
+        self.c = center.iter().map(|&c_i| solution.value(c_i)).collect();
+        Ok(())
+    }
+
+    /// Center $c$ and the span of the generators
+    fn affine_hull(&self) -> Result<AffineHull, SetOperationError> {
+        AffineHull::from_directions(self.c.clone(), &self.G)
+    }
+
     fn degenerate(&self) -> bool {
         self.n_generators() == 0 || rank(&self.G).unwrap() < self.dim()
     }
 
+    /// $c \pm \sum_i |g_i|$
+    fn interval_hull(&self) -> Result<Interval, SetOperationError> {
+        let radius = self.G.abs().sum_axis(Axis(0));
+        Interval::new(&self.c - &radius, &self.c + &radius).map_err(|e| {
+            SetOperationError::DataConversionError {
+                source: Box::new(e),
+            }
+        })
+    }
+
     fn contains_point(&self, point: &Array1<f64>) -> Result<bool, SetOperationError> {
         Ok(self.zonotope_norm(point)? <= 1.0 + 1e-9)
     }
+
+    /// Fully corrective Frank–Wolfe on the support function of the zonotope
+    fn closest_point(&self, point: &Array1<f64>) -> Result<Array1<f64>, SetOperationError> {
+        crate::gjk::frank_wolfe_closest_point(self, point)
+    }
 }
 
 #[cfg(test)]
@@ -232,6 +547,25 @@ impl GeoSet for Zonotope {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_convex_hull_outer_approximation() {
+        let first = Zonotope::from_random(2, 3, false).unwrap();
+        let second = Zonotope::from_random(2, 5, false)
+            .unwrap()
+            .translate(&array![1.0, 2.0])
+            .unwrap();
+        let hull = first.convex_hull(&second).unwrap();
+        assert_eq!(hull.n_generators(), 11);
+
+        for angle in (0..12).map(|k| k as f64 * std::f64::consts::PI / 6.) {
+            let direction = array![angle.cos(), angle.sin()];
+            let (_, first_value) = first.support_function(direction.clone()).unwrap();
+            let (_, second_value) = second.support_function(direction.clone()).unwrap();
+            let (_, value) = hull.support_function(direction).unwrap();
+            assert!(value >= first_value.max(second_value) - 1e-9);
+        }
+    }
+
     #[test]
     fn test_zonotope_new() {
         let _ = Zonotope::new(Array::ones((5, 2)), Array::zeros(2)).unwrap();
@@ -241,6 +575,74 @@ mod tests {
         assert!(zono.is_err());
     }
 
+    #[test]
+    fn test_reduce_order() {
+        let methods = [
+            ReductionMethod::Girard,
+            ReductionMethod::Combastel,
+            ReductionMethod::Pca,
+        ];
+        let zono = Zonotope::from_random(3, 20, false).unwrap();
+        let directions = Array2::random((50, 3), Uniform::new(-1.0, 1.0));
+
+        for method in methods {
+            for target_order in 1..4 {
+                let reduced = zono.reduce_order(target_order, method).unwrap();
+                assert!(reduced.n_generators() <= 3 * target_order);
+                assert!(reduced.c.abs_diff_eq(&zono.c, 1e-12));
+
+                // Over-approximation: the support function may only grow
+                for direction in directions.outer_iter() {
+                    let (_, value) = zono.support_function(direction.to_owned()).unwrap();
+                    let (_, reduced_value) =
+                        reduced.support_function(direction.to_owned()).unwrap();
+                    assert!(reduced_value >= value - 1e-9);
+                }
+            }
+        }
+
+        // Order one with Girard's method gives the interval hull
+        let reduced = zono.reduce_order(1, ReductionMethod::Girard).unwrap();
+        let hull = zono.interval_hull().unwrap();
+        assert!(
+            reduced
+                .interval_hull()
+                .unwrap()
+                .ub()
+                .abs_diff_eq(hull.ub(), 1e-12)
+        );
+        assert!((reduced.volume().unwrap() - hull.volume().unwrap()).abs() < 1e-9);
+
+        // Nothing to reduce
+        let reduced = zono.reduce_order(7, ReductionMethod::Girard).unwrap();
+        assert_eq!(reduced.n_generators(), 20);
+        assert!(zono.reduce_order(0, ReductionMethod::Pca).is_err());
+    }
+
+    #[test]
+    fn test_pontryagin_difference_inner_approximation() {
+        let zono = Zonotope::new(
+            array![[1.0, 1.0], [1.0, -1.0], [0.5, 0.0]],
+            array![0.0, 0.0],
+        )
+        .unwrap();
+        let subtrahend = Zonotope::new(array![[0.2, 0.0], [0.0, 0.1]], array![0.1, 0.0]).unwrap();
+        let difference = zono.pontryagin_difference(&subtrahend).unwrap();
+
+        // Every point of the inner approximation shifted by the subtrahend stays in the zonotope
+        let sum = difference.minkowski_sum(&subtrahend).unwrap();
+        for vertex in sum.to_vertices().unwrap().outer_iter() {
+            assert!(zono.contains_point(&vertex.to_owned()).unwrap());
+        }
+
+        // Exact for a subtrahend that is a scaled copy
+        let scaled = zono.matmul(&(Array2::eye(2) * 0.25)).unwrap();
+        let difference = zono.pontryagin_difference(&scaled).unwrap();
+        assert!(
+            (difference.volume().unwrap() - 0.75_f64.powi(2) * zono.volume().unwrap()).abs() < 1e-6
+        );
+    }
+
     #[test]
     fn test_matmul_rotation() {
         let G = array![[1.0, 0.0], [0.0, 1.0]]; // Two generators: [1,0] and [0,1]
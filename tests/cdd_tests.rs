@@ -1,6 +1,8 @@
-/// Tests for the `compute_polytope_vertices` function in the cddlib_rs module.
-use geosets_rs::compute_polytope_vertices;
+//! Tests for the `compute_polytope_vertices` function in the cddlib_rs module.
 use geosets_rs::sets::errors::SetOperationError;
+use geosets_rs::{
+    compute_polyhedron_generators, compute_polytope_halfspaces, compute_polytope_vertices,
+};
 use ndarray::{Array2, array};
 use std::collections::HashSet;
 
@@ -386,3 +388,48 @@ fn test_large_coordinates() {
     let max_coord: f64 = vertices.iter().fold(0.0_f64, |a, &b| a.max(b));
     assert!(max_coord >= 1e6 - 1e-6);
 }
+
+#[test]
+fn test_unbounded_polyhedron_generators() {
+    // Positive quadrant: x >= 0, y >= 0
+    let a = array![[-1.0, 0.0], [0.0, -1.0]];
+    let b = array![0.0, 0.0];
+
+    let generators = compute_polyhedron_generators(&a, &b).unwrap();
+    assert!(!generators.is_bounded());
+    assert_eq!(generators.vertices.nrows(), 1);
+    assert!(generators.vertices.row(0).iter().all(|x| x.abs() < 1e-9));
+    assert_eq!(generators.rays.nrows(), 2);
+    assert_eq!(generators.lines.nrows(), 0);
+    for ray in generators.rays.outer_iter() {
+        assert!(a.dot(&ray).iter().all(|&x| x <= 1e-9));
+    }
+}
+
+#[test]
+fn test_halfspace_generators() {
+    // x <= 5 has the line along the y-axis and the ray -e_x
+    let a = array![[1.0, 0.0]];
+    let b = array![5.0];
+
+    let generators = compute_polyhedron_generators(&a, &b).unwrap();
+    assert_eq!(generators.lines.nrows(), 1);
+    assert!(generators.lines[[0, 0]].abs() < 1e-9);
+    assert_eq!(generators.rays.nrows(), 1);
+    assert!(generators.rays[[0, 0]] < 0.0);
+    assert_eq!(generators.vertices.nrows(), 1);
+    assert!((generators.vertices[[0, 0]] - 5.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_flat_polytope_halfspaces() {
+    // The segment from (0, 0) to (1, 1) lies on the line x_1 = x_2
+    let vertices = array![[0.0, 0.0], [1.0, 1.0]];
+
+    let halfspaces = compute_polytope_halfspaces(&vertices).unwrap();
+    assert_eq!(halfspaces.a.nrows(), 2);
+    assert_eq!(halfspaces.ae.nrows(), 1);
+    let normal = halfspaces.ae.row(0);
+    assert!((normal[0] + normal[1]).abs() < 1e-9);
+    assert!(halfspaces.be[0].abs() < 1e-9);
+}
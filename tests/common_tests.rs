@@ -1,5 +1,7 @@
 use geosets_rs::order_vertices_clockwise;
 use geosets_rs::sets::GeoSet;
+use geosets_rs::sets::errors::SetOperationError;
+use geosets_rs::{HPolytope, Interval, IsSubset, VPolytope};
 use ndarray::{Array1, Array2, array};
 use ndarray_rand::RandomExt;
 use ndarray_rand::rand_distr::Uniform;
@@ -17,6 +19,8 @@ macro_rules! test_all_geosets {
         #[case::vpolytope(std::marker::PhantomData::<geosets_rs::VPolytope>)]
         #[case::zonotope(std::marker::PhantomData::<geosets_rs::Zonotope>)]
         #[case::interval(std::marker::PhantomData::<geosets_rs::Interval>)]
+        #[case::constrained_zonotope(std::marker::PhantomData::<geosets_rs::ConstrainedZonotope>)]
+        #[case::lazy(std::marker::PhantomData::<geosets_rs::LazySet<geosets_rs::Zonotope>>)]
         fn $test_name<T: GeoSet>(#[case] _marker: std::marker::PhantomData<T>) $test_body
     };
 }
@@ -233,3 +237,327 @@ test_all_geosets!(test_containment_common, {
         }
     }
 });
+
+test_all_geosets!(test_intersection_common, {
+    let set = T::from_unit_box(2);
+    let shifted = set.translate(&array![1.0, 1.0]).unwrap();
+
+    match set.intersection(&shifted) {
+        Ok(intersection) => {
+            let vertices = intersection.to_vertices().unwrap();
+            let expected = array![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+
+            assert_eq!(_vertices_to_set(&vertices), _vertices_to_set(&expected));
+            assert!((intersection.volume().unwrap() - 1.0).abs() < 1e-6);
+        }
+        // Not every representation is closed under intersection
+        Err(SetOperationError::UnsupportedOperation { .. }) => {}
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+
+    // Disjoint sets
+    let far_away = set.translate(&array![3.0, 0.0]).unwrap();
+    match set.intersection(&far_away) {
+        Err(SetOperationError::EmptySet) | Err(SetOperationError::UnsupportedOperation { .. }) => {}
+        _ => panic!("Intersection of disjoint sets should be empty"),
+    }
+});
+
+test_all_geosets!(test_interval_hull_common, {
+    for dim in 2..5 {
+        let set = T::from_unit_box(dim).translate(&Array1::ones(dim)).unwrap();
+        let hull = set.interval_hull().unwrap();
+
+        assert!(hull.lb().abs_diff_eq(&Array1::zeros(dim), 1e-9));
+        assert!(hull.ub().abs_diff_eq(&Array1::from_elem(dim, 2.0), 1e-9));
+    }
+});
+
+test_all_geosets!(test_pontryagin_difference_common, {
+    for dim in 2..4 {
+        let set = T::from_unit_box(dim);
+        let subtrahend = geosets_rs::Interval::from_unit_box(dim)
+            .matmul(&(Array2::eye(dim) * 0.5))
+            .unwrap();
+
+        match set.pontryagin_difference(&subtrahend) {
+            Ok(difference) => {
+                let hull = difference.interval_hull().unwrap();
+                assert!(hull.lb().abs_diff_eq(&Array1::from_elem(dim, -0.5), 1e-6));
+                assert!(hull.ub().abs_diff_eq(&Array1::from_elem(dim, 0.5), 1e-6));
+                assert!((difference.volume().unwrap() - 1.0).abs() < 1e-6);
+            }
+            Err(SetOperationError::NotImplemented) => {}
+            Err(e) => panic!("Unexpected error: {}", e),
+        }
+
+        // The subtrahend does not fit into the set
+        let larger = geosets_rs::Zonotope::from_unit_box(dim)
+            .matmul(&(Array2::eye(dim) * 1.5))
+            .unwrap();
+        match set.pontryagin_difference(&larger) {
+            Err(SetOperationError::EmptySet) | Err(SetOperationError::NotImplemented) => {}
+            _ => panic!("Difference with a larger set should be empty"),
+        }
+    }
+});
+
+test_all_geosets!(test_cartesian_product_common, {
+    let set = T::from_unit_box(2).translate(&array![1.0, 0.0]).unwrap();
+    let other = T::from_unit_box(1);
+
+    let product = set.cartesian_product(&other).unwrap();
+    assert_eq!(product.dim(), 3);
+    assert!(product.contains_point(&array![1.5, -0.5, 0.5]).unwrap());
+    assert!(!product.contains_point(&array![-0.5, 0.0, 0.0]).unwrap());
+    assert!((product.volume().unwrap() - 8.0).abs() < 1e-6);
+
+    let hull = product.interval_hull().unwrap();
+    assert!(hull.lb().abs_diff_eq(&array![0.0, -1.0, -1.0], 1e-6));
+    assert!(hull.ub().abs_diff_eq(&array![2.0, 1.0, 1.0], 1e-6));
+});
+
+test_all_geosets!(test_project_common, {
+    let set = T::from_unit_box(3)
+        .translate(&array![1.0, 2.0, 3.0])
+        .unwrap();
+
+    let projected = set.project(&[2, 0]).unwrap();
+    assert_eq!(projected.dim(), 2);
+
+    let hull = projected.interval_hull().unwrap();
+    assert!(hull.lb().abs_diff_eq(&array![2.0, 0.0], 1e-6));
+    assert!(hull.ub().abs_diff_eq(&array![4.0, 2.0], 1e-6));
+    assert!((projected.volume().unwrap() - 4.0).abs() < 1e-6);
+
+    assert!(set.project(&[0, 0]).is_err());
+    assert!(set.project(&[3]).is_err());
+});
+
+test_all_geosets!(test_affine_map_common, {
+    for dim in 2..4 {
+        let set = T::from_unit_box(dim);
+
+        // Wide map to the sum of all coordinates
+        let output = set
+            .affine_map(&Array2::ones((1, dim)), &array![1.0])
+            .unwrap();
+        assert_eq!(output.dim(), 1);
+        let hull = output.interval_hull().unwrap();
+        assert!((hull.lb()[0] - (1.0 - dim as f64)).abs() < 1e-6);
+        assert!((hull.ub()[0] - (1.0 + dim as f64)).abs() < 1e-6);
+
+        // Tall map duplicating the first coordinate
+        let mut mat = Array2::zeros((dim + 1, dim));
+        for i in 0..dim {
+            mat[[i, i]] = 1.0;
+        }
+        mat[[dim, 0]] = 1.0;
+        let output = set.affine_map(&mat, &Array1::zeros(dim + 1)).unwrap();
+        assert_eq!(output.dim(), dim + 1);
+        let hull = output.interval_hull().unwrap();
+        assert!(
+            hull.lb()
+                .abs_diff_eq(&Array1::from_elem(dim + 1, -1.0), 1e-6)
+        );
+        assert!(
+            hull.ub()
+                .abs_diff_eq(&Array1::from_elem(dim + 1, 1.0), 1e-6)
+        );
+
+        assert!(
+            set.affine_map(&Array2::ones((2, dim + 1)), &Array1::zeros(2))
+                .is_err()
+        );
+        assert!(
+            set.affine_map(&Array2::ones((2, dim)), &Array1::zeros(3))
+                .is_err()
+        );
+    }
+});
+
+test_all_geosets!(test_matmul_negative_common, {
+    let set = T::from_unit_box(2).translate(&array![1.0, 0.0]).unwrap();
+    let mirrored = set.matmul(&array![[-1.0, 0.0], [0.0, 1.0]]).unwrap();
+
+    let hull = mirrored.interval_hull().unwrap();
+    assert!(hull.lb().abs_diff_eq(&array![-2.0, -1.0], 1e-6));
+    assert!(hull.ub().abs_diff_eq(&array![0.0, 1.0], 1e-6));
+});
+
+test_all_geosets!(test_is_subset_common, {
+    for dim in 2..4 {
+        let set = T::from_unit_box(dim);
+        let shifted = set.translate(&Array1::from_elem(dim, 0.5)).unwrap();
+        let larger = Array2::eye(dim) * 2.0;
+
+        assert!(
+            set.is_subset_of(&HPolytope::from_unit_box(dim).matmul(&larger).unwrap())
+                .unwrap()
+                .is_subset
+        );
+        assert!(
+            set.is_subset_of(&Interval::from_unit_box(dim))
+                .unwrap()
+                .is_subset
+        );
+        assert!(
+            set.is_subset_of(&VPolytope::from_unit_box(dim))
+                .unwrap()
+                .is_subset
+        );
+
+        let check = shifted
+            .is_subset_of(&HPolytope::from_unit_box(dim))
+            .unwrap();
+        assert!(!check.is_subset && check.exact);
+        let check = shifted.is_subset_of(&Interval::from_unit_box(dim)).unwrap();
+        assert!(!check.is_subset && check.exact);
+        let check = shifted
+            .is_subset_of(&VPolytope::from_unit_box(dim))
+            .unwrap();
+        assert!(!check.is_subset && check.exact);
+    }
+});
+
+test_all_geosets!(test_is_disjoint_common, {
+    for dim in 2..4 {
+        let set = T::from_unit_box(dim);
+        let mut shift = Array1::zeros(dim);
+        shift[0] = 2.5;
+        shift[1] = 0.5;
+        let far_away = set.translate(&shift).unwrap();
+
+        let hyperplane = set.is_disjoint(&far_away).unwrap().unwrap();
+        let (_, upper) = set.support_function(hyperplane.normal.clone()).unwrap();
+        let (_, lower) = far_away.support_function(-&hyperplane.normal).unwrap();
+        assert!((hyperplane.normal.dot(&hyperplane.normal) - 1.0).abs() < 1e-9);
+        assert!(upper < hyperplane.offset && hyperplane.offset < -lower);
+
+        // Overlapping and touching sets
+        let overlapping = set.translate(&Array1::from_elem(dim, 1.0)).unwrap();
+        assert!(set.is_disjoint(&overlapping).unwrap().is_none());
+        let mut shift = Array1::zeros(dim);
+        shift[0] = 2.0;
+        let touching = set.translate(&shift).unwrap();
+        assert!(set.is_disjoint(&touching).unwrap().is_none());
+
+        // Different representations
+        let ellipsoid = geosets_rs::Ellipsoid::from_unit_box(dim)
+            .translate(&Array1::from_elem(dim, 2.0))
+            .unwrap();
+        assert!(set.is_disjoint(&ellipsoid).unwrap().is_some());
+        let ellipsoid = geosets_rs::Ellipsoid::from_unit_box(dim)
+            .translate(&Array1::from_elem(dim, 1.5))
+            .unwrap();
+        assert!(set.is_disjoint(&ellipsoid).unwrap().is_none());
+    }
+});
+
+test_all_geosets!(test_point_distance_common, {
+    for dim in 2..4 {
+        let set = T::from_unit_box(dim);
+
+        let mut point = Array1::zeros(dim);
+        point[0] = 3.0;
+        point[1] = 0.5;
+        let mut expected = point.clone();
+        expected[0] = 1.0;
+        assert!(
+            set.closest_point(&point)
+                .unwrap()
+                .abs_diff_eq(&expected, 1e-6)
+        );
+        assert!((set.distance_to_point(&point).unwrap() - 2.0).abs() < 1e-6);
+        assert!((set.signed_distance(&point).unwrap() - 2.0).abs() < 1e-6);
+
+        // Closest to a corner
+        let point = Array1::from_elem(dim, 2.0);
+        let expected_distance = (dim as f64).sqrt();
+        assert!((set.distance_to_point(&point).unwrap() - expected_distance).abs() < 1e-6);
+
+        // Inside the set
+        let mut point = Array1::zeros(dim);
+        point[1] = 0.25;
+        assert!(set.closest_point(&point).unwrap().abs_diff_eq(&point, 1e-6));
+        assert!(set.distance_to_point(&point).unwrap() < 1e-6);
+        assert!((set.signed_distance(&point).unwrap() + 0.75).abs() < 1e-6);
+
+        // Just outside and on the boundary the signed distance is not negative
+        let mut point = Array1::zeros(dim);
+        point[0] = 1.0 + 1e-7;
+        assert!(set.signed_distance(&point).unwrap() > 0.0);
+        point[0] = 1.0;
+        assert!(set.signed_distance(&point).unwrap().is_sign_positive());
+    }
+});
+
+test_all_geosets!(test_convex_hull_common, {
+    for dim in 2..4 {
+        let set = T::from_unit_box(dim);
+        let shifted = set.translate(&Array1::from_elem(dim, 3.0)).unwrap();
+        let hull = set.convex_hull(&shifted).unwrap();
+
+        let bounds = hull.interval_hull().unwrap();
+        assert!(bounds.lb().abs_diff_eq(&Array1::from_elem(dim, -1.0), 1e-6));
+        assert!(bounds.ub().abs_diff_eq(&Array1::from_elem(dim, 4.0), 1e-6));
+
+        // Between both sets, but in neither of them
+        let mut point = Array1::zeros(dim);
+        point[1] = 1.5;
+        assert!(hull.contains_point(&point).unwrap());
+        assert!(hull.contains_point(&Array1::from_elem(dim, 1.5)).unwrap());
+
+        let far_shifted = set.translate(&Array1::from_elem(dim, -3.0)).unwrap();
+        let hull = T::convex_hull_many(&[set.clone(), shifted, far_shifted]).unwrap();
+        for corner in [-4.0, 0.0, 4.0] {
+            assert!(
+                hull.contains_point(&Array1::from_elem(dim, corner))
+                    .unwrap()
+            );
+        }
+
+        let other_dim = T::from_unit_box(dim + 1);
+        assert!(set.convex_hull(&other_dim).is_err());
+        assert!(T::convex_hull_many(&[]).is_err());
+    }
+});
+
+test_all_geosets!(test_relative_dim_common, {
+    let set = T::from_unit_box(3);
+    assert_eq!(set.relative_dim().unwrap(), 3);
+
+    // Singular maps flatten the set onto a plane and a line
+    let plane = set
+        .matmul(&Array2::from_diag(&array![1.0, 1.0, 0.0]))
+        .unwrap();
+    assert_eq!(plane.relative_dim().unwrap(), 2);
+    assert!(plane.volume().unwrap().abs() < 1e-9);
+
+    let line = set
+        .matmul(&Array2::from_diag(&array![2.0, 0.0, 0.0]))
+        .unwrap();
+    let hull = line.affine_hull().unwrap();
+    assert_eq!(hull.dim(), 1);
+    assert!(hull.contains_point(&array![5.0, 0.0, 0.0], 1e-6));
+});
+
+test_all_geosets!(test_overapproximate_template_common, {
+    for dim in 2..4 {
+        let set = T::from_unit_box(dim).translate(&Array1::ones(dim)).unwrap();
+
+        // The box template reproduces the interval hull
+        let outer = set
+            .overapproximate_template(&geosets_rs::sets::template::box_directions(dim))
+            .unwrap();
+        assert!((outer.volume().unwrap() - 2.0_f64.powi(dim as i32)).abs() < 1e-6);
+
+        let octagon = set
+            .overapproximate_template(&geosets_rs::sets::template::octagon_directions(dim))
+            .unwrap();
+        assert!(octagon.contains_point(&Array1::ones(dim)).unwrap());
+        for vertex in set.to_vertices().unwrap().outer_iter() {
+            assert!(octagon.contains_point(&vertex.to_owned()).unwrap());
+        }
+    }
+});
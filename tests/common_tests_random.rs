@@ -1,5 +1,5 @@
 use geosets_rs::sets::GeoSet;
-use ndarray::{Array1, Array2, array};
+use ndarray::{Array1, Array2, Axis, array};
 use rstest::rstest;
 use std::collections::HashSet;
 
@@ -14,6 +14,7 @@ macro_rules! test_all_random_geosets {
         #[case::vpolytope(std::marker::PhantomData::<geosets_rs::VPolytope>)]
         #[case::zonotope(std::marker::PhantomData::<geosets_rs::Zonotope>)]
         #[case::interval(std::marker::PhantomData::<geosets_rs::Interval>)]
+        #[case::constrained_zonotope(std::marker::PhantomData::<geosets_rs::ConstrainedZonotope>)]
         fn $test_name<T: GeoSet + CreateRandom>(#[case] _marker: std::marker::PhantomData<T>) $test_body
     };
 }
@@ -50,6 +51,13 @@ impl CreateRandom for geosets_rs::Interval {
     }
 }
 
+impl CreateRandom for geosets_rs::ConstrainedZonotope {
+    fn create_random(dim: usize) -> Self {
+        // One constraint on dim + 3 generators keeps the set full-dimensional
+        geosets_rs::ConstrainedZonotope::from_random(dim, dim + 3, 1).unwrap()
+    }
+}
+
 // Used for testing
 fn _vertices_to_set(vertices: &Array2<f64>) -> HashSet<Vec<u64>> {
     vertices
@@ -196,3 +204,17 @@ test_all_random_geosets!(test_support_function_random, {
         }
     }
 });
+
+test_all_random_geosets!(test_interval_hull_random, {
+    for dim in 2..4 {
+        let set = T::create_random(dim);
+        let hull = set.interval_hull().unwrap();
+        let vertices = set.to_vertices().unwrap();
+
+        // The hull is tight: its bounds are attained by the vertices
+        let lb = vertices.fold_axis(Axis(0), f64::INFINITY, |&acc, &x| acc.min(x));
+        let ub = vertices.fold_axis(Axis(0), f64::NEG_INFINITY, |&acc, &x| acc.max(x));
+        assert!(hull.lb().abs_diff_eq(&lb, 1e-6));
+        assert!(hull.ub().abs_diff_eq(&ub, 1e-6));
+    }
+});
@@ -0,0 +1,245 @@
+//! Tests for the conversions between set representations.
+use geosets_rs::sets::GeoSet;
+use geosets_rs::{HPolytope, Interval, VPolytope, Zonotope};
+use ndarray::{Array2, array};
+use std::collections::HashSet;
+
+// Rounds vertices to a fixed precision before comparing them as sets
+fn _rounded_vertex_set(vertices: &Array2<f64>) -> HashSet<Vec<i64>> {
+    vertices
+        .outer_iter()
+        .map(|row| row.iter().map(|&x| (x * 1e6).round() as i64).collect())
+        .collect()
+}
+
+fn _assert_unit_rows(hpoly: &HPolytope) {
+    for row in hpoly.A().outer_iter() {
+        assert!(
+            (row.dot(&row).sqrt() - 1.0).abs() < 1e-9,
+            "Rows should be normalized"
+        );
+    }
+}
+
+#[test]
+fn test_vpolytope_to_hpolytope_triangle() {
+    let vpoly = VPolytope::new(array![[0.0, 0.0], [2.0, 0.0], [0.0, 2.0]]).unwrap();
+    let hpoly = vpoly.to_hpolytope().unwrap();
+
+    assert_eq!(hpoly.n_constraints(), 3, "A triangle has exactly 3 facets");
+    _assert_unit_rows(&hpoly);
+
+    assert!(hpoly.contains_point(&array![0.5, 0.5]).unwrap());
+    assert!(!hpoly.contains_point(&array![1.5, 1.5]).unwrap());
+    assert_eq!(
+        _rounded_vertex_set(&hpoly.to_vertices().unwrap()),
+        _rounded_vertex_set(&vpoly.to_vertices().unwrap())
+    );
+}
+
+#[test]
+fn test_vpolytope_to_hpolytope_removes_interior_points() {
+    // Unit box with an interior point and a point on an edge
+    let vpoly = VPolytope::new(array![
+        [-1.0, -1.0],
+        [1.0, -1.0],
+        [1.0, 1.0],
+        [-1.0, 1.0],
+        [0.2, 0.1],
+        [0.0, 1.0],
+    ])
+    .unwrap();
+    let hpoly = vpoly.to_hpolytope().unwrap();
+
+    assert_eq!(hpoly.n_constraints(), 4);
+    _assert_unit_rows(&hpoly);
+    assert!((hpoly.volume().unwrap() - 4.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_vpolytope_to_hpolytope_random() {
+    for dim in 2..4 {
+        let vpoly = VPolytope::from_random(dim, 10).unwrap();
+        let hpoly = vpoly.to_hpolytope().unwrap();
+
+        _assert_unit_rows(&hpoly);
+        assert!((hpoly.volume().unwrap() - vpoly.volume().unwrap()).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn test_vpolytope_to_hpolytope_flat() {
+    // Segment in 2D, bounded by an equality and two endpoints
+    let vertices = array![[0.0, 0.0], [1.0, 1.0]];
+    let hpoly = VPolytope::new(vertices.clone())
+        .unwrap()
+        .to_hpolytope()
+        .unwrap();
+
+    assert_eq!(hpoly.n_constraints(), 2);
+    assert_eq!(hpoly.n_equalities(), 1);
+
+    assert_eq!(
+        _rounded_vertex_set(&hpoly.to_vertices().unwrap()),
+        _rounded_vertex_set(&vertices)
+    );
+    assert!(!hpoly.contains_point(&array![0.5, 0.6]).unwrap());
+    assert!(!hpoly.contains_point(&array![1.5, 1.5]).unwrap());
+}
+
+#[test]
+fn test_zonotope_to_hpolytope_unit_box() {
+    let zono = Zonotope::from_unit_box(3);
+    let hpoly = zono.to_hpolytope().unwrap();
+
+    assert_eq!(hpoly.n_constraints(), 6);
+    _assert_unit_rows(&hpoly);
+    assert!((hpoly.volume().unwrap() - 8.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_zonotope_to_hpolytope_random() {
+    for dim in 2..4 {
+        let zono = Zonotope::from_random(dim, dim + 3, false).unwrap();
+        let hpoly = zono.to_hpolytope().unwrap();
+
+        _assert_unit_rows(&hpoly);
+        assert_eq!(
+            _rounded_vertex_set(&hpoly.to_vertices().unwrap()),
+            _rounded_vertex_set(&zono.to_vertices().unwrap())
+        );
+        assert!((hpoly.volume().unwrap() - zono.volume().unwrap()).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn test_zonotope_to_hpolytope_duplicate_generators() {
+    // Parallel generators share their facets
+    let zono = Zonotope::new(
+        array![[1.0, 0.0], [2.0, 0.0], [0.0, 1.0]],
+        array![1.0, -1.0],
+    )
+    .unwrap();
+    let hpoly = zono.to_hpolytope().unwrap();
+
+    assert_eq!(hpoly.n_constraints(), 4);
+    let expected = array![[-2.0, -2.0], [4.0, -2.0], [4.0, 0.0], [-2.0, 0.0]];
+    assert_eq!(
+        _rounded_vertex_set(&hpoly.to_vertices().unwrap()),
+        _rounded_vertex_set(&expected)
+    );
+}
+
+#[test]
+fn test_zonotope_to_hpolytope_degenerate() {
+    // Flat zonotope in 3D spanned by two generators
+    let zono = Zonotope::new(
+        array![[1.0, 0.0, 0.0], [0.0, 1.0, 1.0]],
+        array![0.0, 0.0, 1.0],
+    )
+    .unwrap();
+    let hpoly = zono.to_hpolytope().unwrap();
+    assert_eq!(hpoly.n_constraints(), 4);
+    assert_eq!(hpoly.n_equalities(), 1);
+
+    let expected = array![
+        [1.0, 1.0, 2.0],
+        [1.0, -1.0, 0.0],
+        [-1.0, 1.0, 2.0],
+        [-1.0, -1.0, 0.0]
+    ];
+    assert_eq!(
+        _rounded_vertex_set(&hpoly.to_vertices().unwrap()),
+        _rounded_vertex_set(&expected)
+    );
+}
+
+#[test]
+fn test_zonotope_to_hpolytope_point() {
+    let zono = Zonotope::new(Array2::zeros((2, 2)), array![1.0, 2.0]).unwrap();
+    let hpoly = zono.to_hpolytope().unwrap();
+
+    assert_eq!(
+        _rounded_vertex_set(&hpoly.to_vertices().unwrap()),
+        _rounded_vertex_set(&array![[1.0, 2.0]])
+    );
+}
+
+#[test]
+fn test_interval_to_zonotope() {
+    let interval = Interval::new(array![-1.0, 0.0, 2.0], array![1.0, 4.0, 3.0]).unwrap();
+    let zono = Zonotope::from(interval.clone());
+
+    assert_eq!(zono.n_generators(), 3);
+    assert_eq!(zono.center().unwrap(), array![0.0, 2.0, 2.5]);
+    assert_eq!(
+        _rounded_vertex_set(&zono.to_vertices().unwrap()),
+        _rounded_vertex_set(&interval.to_vertices().unwrap())
+    );
+    assert!((zono.volume().unwrap() - interval.volume().unwrap()).abs() < 1e-9);
+}
+
+#[test]
+fn test_interval_to_hpolytope() {
+    let interval = Interval::new(array![-1.0, 0.0, 2.0], array![1.0, 4.0, 3.0]).unwrap();
+    let hpoly = HPolytope::from(interval.clone());
+
+    assert_eq!(hpoly.n_constraints(), 6);
+    assert_eq!(
+        _rounded_vertex_set(&hpoly.to_vertices().unwrap()),
+        _rounded_vertex_set(&interval.to_vertices().unwrap())
+    );
+    assert!((hpoly.volume().unwrap() - interval.volume().unwrap()).abs() < 1e-6);
+}
+
+#[test]
+fn test_interval_to_vpolytope() {
+    let interval = Interval::new(array![-1.0, 0.0, 2.0], array![1.0, 4.0, 3.0]).unwrap();
+    let vpoly = VPolytope::from(interval.clone());
+
+    assert_eq!(vpoly.n_vertices(), 8);
+    assert!((vpoly.volume().unwrap() - interval.volume().unwrap()).abs() < 1e-6);
+}
+
+#[test]
+fn test_point_interval_to_hpolytope() {
+    let interval = Interval::new(array![1.0, -2.0], array![1.0, -2.0]).unwrap();
+    let hpoly = HPolytope::from(interval);
+
+    assert_eq!(hpoly.n_equalities(), 2);
+    assert!(hpoly.contains_point(&array![1.0, -2.0]).unwrap());
+    assert!(!hpoly.contains_point(&array![1.0, -1.9]).unwrap());
+}
+
+#[test]
+fn test_degenerate_interval_conversions() {
+    // Flat in the second dimension
+    let interval = Interval::new(array![-1.0, 2.0, 0.0], array![1.0, 2.0, 1.0]).unwrap();
+    let expected = array![
+        [-1.0, 2.0, 0.0],
+        [1.0, 2.0, 0.0],
+        [-1.0, 2.0, 1.0],
+        [1.0, 2.0, 1.0]
+    ];
+
+    let zono = Zonotope::from(interval.clone());
+    assert_eq!(zono.n_generators(), 2);
+    assert_eq!(
+        _rounded_vertex_set(&zono.to_hpolytope().unwrap().to_vertices().unwrap()),
+        _rounded_vertex_set(&expected)
+    );
+
+    let hpoly = HPolytope::from(interval.clone());
+    assert_eq!(hpoly.n_equalities(), 1);
+    assert_eq!(hpoly.n_constraints(), 4);
+    assert!(hpoly.contains_point(&array![0.0, 2.0, 0.5]).unwrap());
+    assert!(!hpoly.contains_point(&array![0.0, 2.1, 0.5]).unwrap());
+    assert_eq!(
+        _rounded_vertex_set(&hpoly.to_vertices().unwrap()),
+        _rounded_vertex_set(&expected)
+    );
+
+    let vpoly = VPolytope::from(interval);
+    assert_eq!(vpoly.n_vertices(), 4);
+    assert!(vpoly.contains_point(&array![0.0, 2.0, 0.5]).unwrap());
+}
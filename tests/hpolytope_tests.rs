@@ -1,8 +1,255 @@
+use geosets_rs::sets::errors::SetOperationError;
 use geosets_rs::sets::hpolytope::HPolytope;
-use ndarray::Array;
+use geosets_rs::{GeoSet, IsSubset, VPolytope};
+use ndarray::{Array, Array2, array};
+use ndarray_rand::RandomExt;
+use ndarray_rand::rand_distr::Uniform;
 
 #[test]
 fn test_polytope_new() {
     let _ = HPolytope::new(Array::ones((2, 2)), Array::ones(2)).unwrap();
     let _ = HPolytope::new(Array::ones((5, 2)), Array::ones(5)).unwrap();
 }
+
+#[test]
+fn test_project_matches_vertex_projection() {
+    for _ in 0..5 {
+        let hpoly = HPolytope::from_random(4, 6).unwrap();
+        let projected = hpoly.project(&[3, 1]).unwrap();
+
+        let vpoly = VPolytope::new(hpoly.to_vertices().unwrap()).unwrap();
+        let expected = vpoly.project(&[3, 1]).unwrap();
+
+        assert!((projected.volume().unwrap() - expected.volume().unwrap()).abs() < 1e-6);
+        for vertex in expected.to_vertices().unwrap().outer_iter() {
+            let vertex = vertex.to_owned();
+            let residual = projected.A().dot(&vertex) - projected.b();
+            assert!(residual.iter().all(|&r| r <= 1e-6));
+        }
+    }
+}
+
+/// Compares the support functions of the image with the mapped vertices
+fn _assert_image_matches_vertices(hpoly: &HPolytope, mat: &Array2<f64>) {
+    let image = hpoly.matmul(mat).unwrap();
+    assert_eq!(image.dim(), mat.nrows());
+
+    let mapped_vertices = hpoly.to_vertices().unwrap().dot(&mat.t());
+    let directions = Array2::random((20, mat.nrows()), Uniform::new(-1.0, 1.0));
+    for direction in directions.outer_iter() {
+        let (_, value) = image.support_function(direction.to_owned()).unwrap();
+        let expected = mapped_vertices
+            .dot(&direction)
+            .fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+        assert!((value - expected).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn test_matmul_invertible() {
+    let hpoly = HPolytope::from_unit_box(2)
+        .translate(&array![1.0, 0.0])
+        .unwrap();
+    let rotated = hpoly.matmul(&array![[0.0, -1.0], [1.0, 0.0]]).unwrap();
+    assert!(rotated.contains_point(&array![0.0, 1.5]).unwrap());
+    assert!(!rotated.contains_point(&array![1.5, 0.0]).unwrap());
+
+    _assert_image_matches_vertices(
+        &HPolytope::from_random(3, 4).unwrap(),
+        &array![[1.0, 2.0, 0.0], [0.0, 1.0, -1.0], [0.5, 0.0, 1.0]],
+    );
+}
+
+#[test]
+fn test_matmul_singular() {
+    // Integrator chain with a deadbeat gain maps everything onto a line
+    let image = HPolytope::from_unit_box(2)
+        .matmul(&array![[1.0, 1.0], [0.0, 0.0]])
+        .unwrap();
+    assert!(image.contains_point(&array![1.5, 0.0]).unwrap());
+    assert!(!image.contains_point(&array![1.5, 0.1]).unwrap());
+
+    let hull = image.interval_hull().unwrap();
+    assert!(hull.lb().abs_diff_eq(&array![-2.0, 0.0], 1e-6));
+    assert!(hull.ub().abs_diff_eq(&array![2.0, 0.0], 1e-6));
+
+    _assert_image_matches_vertices(
+        &HPolytope::from_random(3, 4).unwrap(),
+        &array![[1.0, 2.0, 0.0], [0.0, 1.0, -1.0], [1.0, 3.0, -1.0]],
+    );
+
+    // Zero matrix
+    let image = HPolytope::from_unit_box(2)
+        .matmul(&Array2::zeros((3, 2)))
+        .unwrap();
+    assert!(image.contains_point(&array![0.0, 0.0, 0.0]).unwrap());
+    assert!(!image.contains_point(&array![0.1, 0.0, 0.0]).unwrap());
+
+    let empty = HPolytope::new(array![[1.0, 0.0], [-1.0, 0.0]], array![-1.0, -1.0]).unwrap();
+    let image = empty.matmul(&Array2::zeros((2, 2))).unwrap();
+    assert!(image.empty().unwrap());
+
+    // Empty inputs stay empty for every rank
+    let image = empty.matmul(&array![[1.0, 0.0], [0.0, 0.0]]).unwrap();
+    assert!(image.empty().unwrap());
+    let image = empty.matmul(&array![[2.0, 1.0], [0.0, 1.0]]).unwrap();
+    assert!(image.empty().unwrap());
+}
+
+#[test]
+fn test_matmul_non_square() {
+    // Wide
+    _assert_image_matches_vertices(
+        &HPolytope::from_random(3, 4).unwrap(),
+        &array![[1.0, -2.0, 0.5]],
+    );
+    _assert_image_matches_vertices(
+        &HPolytope::from_random(3, 4).unwrap(),
+        &array![[1.0, -2.0, 0.5], [0.0, 1.0, 1.0]],
+    );
+
+    // Tall
+    _assert_image_matches_vertices(
+        &HPolytope::from_random(2, 4).unwrap(),
+        &array![[1.0, 0.0], [0.0, 1.0], [1.0, 1.0]],
+    );
+
+    // The lift into the column space is described by equality constraints
+    let lifted = HPolytope::from_unit_box(2)
+        .matmul(&array![[1.0, 0.0], [0.0, 1.0], [1.0, 1.0]])
+        .unwrap();
+    assert_eq!(lifted.n_constraints(), 4);
+    assert_eq!(lifted.n_equalities(), 1);
+    assert!(lifted.contains_point(&array![0.5, 0.5, 1.0]).unwrap());
+    assert!(!lifted.contains_point(&array![0.5, 0.5, 0.5]).unwrap());
+}
+
+#[test]
+fn test_unbounded_polyhedron() {
+    // x_1 <= 5, x_2 >= -1
+    let hpoly = HPolytope::new(array![[1.0, 0.0], [0.0, -1.0]], array![5.0, 1.0]).unwrap();
+    assert!(!hpoly.is_bounded().unwrap());
+    assert!(HPolytope::from_unit_box(2).is_bounded().unwrap());
+
+    let (_, value) = hpoly.support_function(array![1.0, 0.0]).unwrap();
+    assert!((value - 5.0).abs() < 1e-9);
+
+    let (ray, value) = hpoly.support_function(array![-1.0, 1.0]).unwrap();
+    assert_eq!(value, f64::INFINITY);
+    assert!(ray.dot(&array![-1.0, 1.0]) > 0.0);
+    assert!(hpoly.A().dot(&ray).iter().all(|&x| x <= 1e-9));
+
+    assert!(matches!(
+        hpoly.to_vertices(),
+        Err(SetOperationError::UnsupportedOperation { .. })
+    ));
+    assert!(hpoly.contains_point(&array![-100.0, 100.0]).unwrap());
+}
+
+#[test]
+fn test_closest_point_qp() {
+    // Closest points of an unbounded polyhedron lie on its faces
+    let hpoly = HPolytope::new(array![[1.0, 0.0], [0.0, -1.0]], array![5.0, 1.0]).unwrap();
+    assert!(
+        hpoly
+            .closest_point(&array![8.0, -4.0])
+            .unwrap()
+            .abs_diff_eq(&array![5.0, -1.0], 1e-9)
+    );
+    assert!((hpoly.signed_distance(&array![4.0, 10.0]).unwrap() + 1.0).abs() < 1e-9);
+
+    // Points are projected into the plane of a polytope with equality constraints
+    let cube = HPolytope::from_unit_box(3);
+    let square = HPolytope::new_with_equalities(
+        cube.A().clone(),
+        cube.b().clone(),
+        array![[0.0, 0.0, 1.0], [0.0, 0.0, 2.0]],
+        array![0.5, 1.0],
+    )
+    .unwrap();
+    assert!(
+        square
+            .closest_point(&array![3.0, 0.2, -1.5])
+            .unwrap()
+            .abs_diff_eq(&array![1.0, 0.2, 0.5], 1e-9)
+    );
+    assert!((square.distance_to_point(&array![0.0, 0.0, 2.5]).unwrap() - 2.0).abs() < 1e-9);
+    let on_square = square.signed_distance(&array![0.3, 0.3, 0.5]).unwrap();
+    assert!(on_square == 0.0 && on_square.is_sign_positive());
+
+    let empty = HPolytope::new(array![[1.0], [-1.0]], array![-1.0, -1.0]).unwrap();
+    assert!(matches!(
+        empty.closest_point(&array![0.0]),
+        Err(SetOperationError::EmptySet)
+    ));
+}
+
+#[test]
+fn test_equality_constraints() {
+    // Unit cube cut by the plane x_3 = 0.5
+    let cube = HPolytope::from_unit_box(3);
+    let square = HPolytope::new_with_equalities(
+        cube.A().clone(),
+        cube.b().clone(),
+        array![[0.0, 0.0, 1.0]],
+        array![0.5],
+    )
+    .unwrap();
+
+    assert!(!square.empty().unwrap());
+    assert!(square.degenerate());
+    assert_eq!(square.volume().unwrap(), 0.0);
+    assert!(square.contains_point(&array![0.3, -0.9, 0.5]).unwrap());
+    assert!(!square.contains_point(&array![0.3, -0.9, 0.4]).unwrap());
+
+    // The Chebyshev center is taken within the plane
+    assert!(
+        square
+            .center()
+            .unwrap()
+            .abs_diff_eq(&array![0.0, 0.0, 0.5], 1e-9)
+    );
+
+    let (support_vector, support_value) = square.support_function(array![1.0, 1.0, 1.0]).unwrap();
+    assert!((support_value - 2.5).abs() < 1e-9);
+    assert!(support_vector.abs_diff_eq(&array![1.0, 1.0, 0.5], 1e-9));
+
+    let vertices = square.to_vertices().unwrap();
+    assert_eq!(vertices.nrows(), 4);
+    assert!(vertices.column(2).iter().all(|&z| (z - 0.5).abs() < 1e-9));
+
+    let shifted = square.translate(&array![0.0, 0.0, 1.0]).unwrap();
+    assert!(shifted.contains_point(&array![0.0, 0.0, 1.5]).unwrap());
+
+    // Equality constraints are kept as equalities
+    let projected = square.project(&[0, 2]).unwrap();
+    assert_eq!(projected.n_equalities(), 1);
+    assert!(projected.contains_point(&array![0.3, 0.5]).unwrap());
+    assert!(!projected.contains_point(&array![0.3, 0.6]).unwrap());
+    assert_eq!(square.project(&[0, 1]).unwrap().n_equalities(), 0);
+
+    let scaled = square.matmul(&(2.0 * Array2::eye(3))).unwrap();
+    assert_eq!(scaled.n_equalities(), 1);
+    assert!(scaled.contains_point(&array![1.5, -2.0, 1.0]).unwrap());
+    assert!(!scaled.contains_point(&array![1.5, -2.0, 0.5]).unwrap());
+
+    let doubled = square.minkowski_sum(&square).unwrap();
+    assert_eq!(doubled.n_equalities(), 1);
+    assert!(doubled.contains_point(&array![1.5, -2.0, 1.0]).unwrap());
+    assert!(!doubled.contains_point(&array![1.5, -2.0, 0.5]).unwrap());
+    assert_eq!(square.minkowski_sum(&cube).unwrap().n_equalities(), 0);
+
+    let flat = VPolytope::new(array![[0.5, 0.5, 0.5], [-0.5, 0.0, 0.5], [0.0, -0.5, 0.5]]).unwrap();
+    assert!(flat.is_subset_of(&square).unwrap().is_subset);
+    assert!(!cube.is_subset_of(&square).unwrap().is_subset);
+
+    assert!(
+        HPolytope::new_with_equalities(
+            cube.A().clone(),
+            cube.b().clone(),
+            array![[1.0, 0.0]],
+            array![0.5],
+        )
+        .is_err()
+    );
+}